@@ -1,22 +1,23 @@
-use std::{f32::consts::PI, time::Duration};
+use std::{
+    f32::consts::{PI, TAU},
+    time::Duration,
+};
 
 use bevy::{
     color::palettes::css::*,
     ecs::{reflect, system::RunSystemOnce, world::Command},
     prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
     sprite::{MaterialMesh2dBundle, Mesh2dHandle},
 };
 use bevy_rapier2d::prelude::*;
 use bevy_tweening::*;
 use lens::ColorMaterialColorLens;
-use rand::Rng;
 
-use crate::{
-    common::*,
-    player::KillPlayerOnTouch,
-    scenes::GameStates,
-    score::{MoneyDrop, SpawnMoney},
-};
+use crate::{common::*, player::KillPlayerOnTouch, scenes::GameStates, score::MoneyDrop};
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Asteroid>();
@@ -27,7 +28,6 @@ pub fn plugin(app: &mut App) {
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
 pub struct Asteroid {
-    pub health: f32,
     pub depth: u32,
 }
 
@@ -46,18 +46,30 @@ fn spawn_asteroid(
     mut cmds: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut r_rng: ResMut<GameRng>,
 ) {
     let depth = spawn.depth;
     let health = (depth + 1) as f32 * 2.5;
     let radius = (depth + 1) as f32 * 100.0;
+    // Projectiles hit the surface, so the weak point sits on the edge.
+    let weak_point = WeakPoint {
+        offset: r_rng.stream(RngStream::Asteroids).circle() * (radius - 10.0),
+        radius: radius * 0.4,
+        multiplier: 2.0,
+    };
     cmds.spawn((
         Name::new(format!("Asteroid - {:?}", depth)),
         StateScoped(GameStates::Match),
-        Asteroid { health, depth },
+        Asteroid { depth },
+        Health::new(health),
         MoneyDrop(depth + 1),
         KillPlayerOnTouch,
+        Knockback::per_hit(10.0),
+        FactionBundle::body(Faction::Environment),
         MaterialMesh2dBundle {
-            mesh: Mesh2dHandle(meshes.add(Circle::new(radius))),
+            mesh: Mesh2dHandle(
+                meshes.add(asteroid_mesh(radius, r_rng.stream(RngStream::Asteroids))),
+            ),
             material: materials.add(Color::from(GREY)),
             transform: Transform::from_translation(spawn.position.extend(0.0)),
             ..default()
@@ -74,30 +86,64 @@ fn spawn_asteroid(
             ..default()
         },
         DestroyOnDeath,
-    ));
+        weak_point,
+    ))
+    .with_children(|asteroid| {
+        asteroid.spawn(MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Circle::new(weak_point.radius * 0.5))),
+            material: materials.add(Color::from(DARK_SLATE_GRAY)),
+            transform: Transform::from_translation(weak_point.offset.extend(0.1)),
+            ..default()
+        });
+    });
+}
+/// Lumpy outline around `radius`, filled in as a triangle fan.
+fn asteroid_mesh(radius: f32, rng: &mut impl RngSampler) -> Mesh {
+    let outline = generate_asteroid_shape(radius, rng);
+    let count = outline.len() as u32;
+
+    let positions: Vec<[f32; 3]> = std::iter::once(Vec2::ZERO)
+        .chain(outline)
+        .map(|v| [v.x, v.y, 0.0])
+        .collect();
+    let uvs: Vec<[f32; 2]> = positions
+        .iter()
+        .map(|[x, y, _]| [0.5 + x / (radius * 2.0), 0.5 - y / (radius * 2.0)])
+        .collect();
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    let indices = (0..count)
+        .flat_map(|i| [0, i + 1, (i + 1) % count + 1])
+        .collect();
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
 }
-fn generate_asteroid_shape(radius: f32) -> Vec<Vec2> {
-    let mut rng = rand::thread_rng();
+
+fn generate_asteroid_shape(radius: f32, rng: &mut impl RngSampler) -> Vec<Vec2> {
     let resolution = 32;
     let delta = 10.0;
-    let angle_dt = PI / resolution as f32;
+    let angle_dt = TAU / resolution as f32;
     let mut vertices = Vec::with_capacity(resolution);
     for i in 0..resolution {
         let angle = i as f32 * angle_dt;
-        vertices.push(Vec2::from_angle(angle) * (radius + rng.gen_range(-delta..delta)));
+        vertices.push(Vec2::from_angle(angle) * (radius + rng.value_range(-delta, delta)));
     }
     vertices
 }
 
+/// Flashes asteroids for hits that actually took health.
 fn on_asteroid_hit(
-    e_hit: Trigger<OnHit>,
+    e_hit: Trigger<OnDamaged>,
     mut cmds: Commands,
-    mut q_asteroids: Query<&mut Asteroid>,
+    q_asteroids: Query<(), With<Asteroid>>,
 ) {
-    if let Ok(mut asteroid) = q_asteroids.get_mut(e_hit.entity()) {
-        let hit = &e_hit.event().0;
-        asteroid.health -= hit.damage;
-
+    if q_asteroids.get(e_hit.entity()).is_ok() {
         let tween = Tween::new(
             EaseFunction::ExponentialOut,
             Duration::from_secs_f32(0.3),
@@ -107,41 +153,20 @@ fn on_asteroid_hit(
             },
         );
 
+        // The hit may have killed the asteroid, which is despawned before this applies.
         cmds.entity(e_hit.entity())
-            .insert(AssetAnimator::new(tween))
-            .insert(ExternalImpulse {
-                impulse: hit.dir.xy() * 10.0,
-                ..default()
-            });
-
-        if asteroid.health <= 0.0 {
-            cmds.trigger_targets(OnDeath(*hit), e_hit.entity());
-        }
+            .try_insert(AssetAnimator::new(tween));
     }
 }
 
 fn on_asteroid_death(
     e_death: Trigger<OnDeath>,
     mut cmds: Commands,
-    mut q_asteroids: Query<(
-        &mut Asteroid,
-        &GlobalTransform,
-        &Collider,
-        &Velocity,
-        Option<&MoneyDrop>,
-    )>,
+    mut q_asteroids: Query<(&mut Asteroid, &GlobalTransform, &Collider, &Velocity)>,
 ) {
-    if let Ok((mut asteroid, asteroid_xform, asteroid_collider, vel, money_drop)) =
+    if let Ok((mut asteroid, asteroid_xform, asteroid_collider, vel)) =
         q_asteroids.get_mut(e_death.entity())
     {
-        if let Some(money) = money_drop {
-            cmds.add(SpawnMoney {
-                money: money.0,
-                position: asteroid_xform.translation().xy(),
-                radial_force: 150.0..300.0,
-            });
-        }
-
         if asteroid.depth > 0 {
             let size = asteroid_collider.as_ball().unwrap().radius();
             let spawn_offset = Vec2::from_angle(PI / 2.0).rotate(vel.linvel.normalize()) * size;
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::player::Player;
+use crate::player::{players_center, Player};
 
 pub fn plugin(app: &mut App) {
     app.register_type::<PlayerCamera>();
@@ -32,14 +32,13 @@ fn camera_follow_player(
     q_player: Query<&GlobalTransform, With<Player>>,
     mut q_camera: Query<(&mut Transform, &PlayerCamera)>,
 ) {
-    if q_player.is_empty() {
+    let Some(center) = players_center(q_player.iter()) else {
         return;
-    }
+    };
 
-    let player_xform = q_player.single();
     for (mut camera_xform, camera) in q_camera.iter_mut() {
-        camera_xform.translation = player_xform
-            .translation()
+        camera_xform.translation = center
+            .extend(camera_xform.translation.z)
             .lerp(camera_xform.translation, camera.smoothness);
     }
 }
@@ -1,28 +1,64 @@
+pub mod crits;
+pub mod faction;
 pub mod follower;
+pub mod knockback;
+pub mod rng;
+pub mod steering;
+pub use crits::*;
+pub use faction::*;
 pub use follower::*;
+pub use knockback::*;
+pub use rng::*;
+pub use steering::*;
 
 use std::time::Duration;
 
-use bevy::{ecs::reflect, prelude::*};
+use bevy::{
+    ecs::{system::RunSystemOnce, world::Command},
+    prelude::*,
+    utils::HashMap,
+};
 use bevy_rapier2d::prelude::*;
 
-use rand::{prelude::*, Rng};
-
 pub fn plugin(app: &mut App) {
+    app.configure_sets(FixedUpdate, GameplaySet.before(PhysicsSet::SyncBackend));
+
     app.register_type::<Lifetime>();
-    app.add_systems(Update, process_lifetimes);
+    app.add_event::<OnExpired>();
+    app.add_systems(FixedUpdate, process_lifetimes.in_set(GameplaySet));
 
-    app.add_event::<OnCollisionEnter>().add_event::<OnHit>();
+    app.add_event::<OnCollisionEnter>()
+        .add_event::<OnHit>()
+        .add_event::<OnDamaged>();
     app.observe(apply_destroy_on_death);
 
+    app.register_type::<Health>();
+    app.add_systems(FixedUpdate, tick_invulnerability.in_set(GameplaySet));
+    app.observe(apply_hit_damage);
+
     app.register_type::<PickUp>();
     app.register_type::<PickUpReceiver>();
     app.add_event::<OnPickedUp>();
-    app.add_systems(Update, (detect_pickups, attract_pickups));
+    app.add_systems(
+        FixedUpdate,
+        (detect_pickups, attract_pickups).in_set(GameplaySet),
+    );
 
-    app.add_plugins(follower::plugin);
+    app.add_plugins((
+        crits::plugin,
+        faction::plugin,
+        follower::plugin,
+        knockback::plugin,
+        rng::plugin,
+        steering::plugin,
+    ));
 }
 
+/// Simulation systems. They run on the fixed timestep, before physics, so a match plays out
+/// the same given the same seed and input.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
 #[derive(Bundle, Default)]
 pub struct PhysicsBundle {
     pub rigidbody: RigidBody,
@@ -40,6 +76,10 @@ pub struct OnCollisionEnter;
 #[derive(Event)]
 pub struct OnHit(pub HitData);
 
+/// Triggered after a hit took health away, with the damage actually dealt.
+#[derive(Event)]
+pub struct OnDamaged(pub HitData);
+
 #[derive(Event)]
 pub struct OnDeath(pub HitData);
 
@@ -52,7 +92,7 @@ fn apply_destroy_on_death(
     q_tags: Query<&DestroyOnDeath>,
 ) {
     if q_tags.get(e_death.entity()).is_ok() {
-        cmds.entity(e_death.entity()).despawn();
+        cmds.entity(e_death.entity()).despawn_recursive();
     }
 }
 
@@ -62,16 +102,175 @@ pub struct HitData {
     pub dir: Dir3,
     pub dealer: Entity,
     pub damage: f32,
+    pub damage_type: DamageType,
+    pub crit: bool,
+    /// Impulse pushing the target away, replacing the damage based push of its [`Knockback`].
+    /// Used by blasts, which push by distance rather than damage.
+    pub impulse: Option<f32>,
+    /// Lands even during the invulnerability window of [`Health`], like deadly contacts.
+    pub ignore_invulnerability: bool,
 }
 impl Default for HitData {
     fn default() -> Self {
         Self {
+            point: Vec3::ZERO,
+            dir: Dir3::Y,
             dealer: Entity::PLACEHOLDER,
-            ..default()
+            damage: 0.0,
+            damage_type: DamageType::default(),
+            crit: false,
+            impulse: None,
+            ignore_invulnerability: false,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum DamageType {
+    #[default]
+    Kinetic,
+    Explosive,
+    Energy,
+}
+
+/// Hit points of anything that can be damaged through [`OnHit`]. Triggers [`OnDeath`] once depleted.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Health {
+    pub max: f32,
+    pub current: f32,
+    /// Seconds during which further hits are ignored after taking damage.
+    pub invulnerability: f32,
+    pub invulnerable_timer: f32,
+    /// Damage multipliers per type, missing types deal regular damage.
+    pub multipliers: HashMap<DamageType, f32>,
+}
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self {
+            max,
+            current: max,
+            invulnerability: 0.0,
+            invulnerable_timer: 0.0,
+            multipliers: HashMap::default(),
+        }
+    }
+
+    pub fn with_invulnerability(mut self, time: f32) -> Self {
+        self.invulnerability = time;
+        self
+    }
+
+    pub fn with_multiplier(mut self, damage_type: DamageType, multiplier: f32) -> Self {
+        self.multipliers.insert(damage_type, multiplier);
+        self
+    }
+
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        self.multipliers.get(&damage_type).copied().unwrap_or(1.0)
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_timer > 0.0
+    }
+}
+
+fn tick_invulnerability(r_time: Res<Time>, mut q_health: Query<&mut Health>) {
+    for mut health in q_health.iter_mut() {
+        if health.is_invulnerable() {
+            health.invulnerable_timer =
+                (health.invulnerable_timer - r_time.delta_seconds()).max(0.0);
+        }
+    }
+}
+
+/// Hits on this entity skip the generic damage observer. Its own observer decides which ones
+/// get through and passes them on with [`ApplyDamage`].
+#[derive(Component, Default)]
+pub struct GuardedHealth;
+
+/// Takes the damage of a hit off the target's [`Health`], like an unguarded [`OnHit`] does.
+pub struct ApplyDamage {
+    pub target: Entity,
+    pub hit: HitData,
+}
+impl Command for ApplyDamage {
+    fn apply(self, world: &mut World) {
+        world.run_system_once_with(self, apply_damage);
+    }
+}
+fn apply_damage(
+    damage: In<ApplyDamage>,
+    mut cmds: Commands,
+    mut q_health: Query<&mut Health>,
+    q_weak_points: Query<(&WeakPoint, &GlobalTransform)>,
+) {
+    if let Ok(mut health) = q_health.get_mut(damage.target) {
+        let weak_point = q_weak_points.get(damage.target).ok();
+        take_damage(
+            &mut cmds,
+            damage.target,
+            &mut health,
+            weak_point,
+            damage.hit,
+        );
+    }
+}
+
+fn apply_hit_damage(
+    e_hit: Trigger<OnHit>,
+    mut cmds: Commands,
+    mut q_health: Query<&mut Health, Without<GuardedHealth>>,
+    q_weak_points: Query<(&WeakPoint, &GlobalTransform)>,
+) {
+    if let Ok(mut health) = q_health.get_mut(e_hit.entity()) {
+        let weak_point = q_weak_points.get(e_hit.entity()).ok();
+        take_damage(
+            &mut cmds,
+            e_hit.entity(),
+            &mut health,
+            weak_point,
+            e_hit.event().0,
+        );
+    }
+}
+
+fn take_damage(
+    cmds: &mut Commands,
+    e: Entity,
+    health: &mut Health,
+    weak_point: Option<(&WeakPoint, &GlobalTransform)>,
+    mut hit: HitData,
+) {
+    if health.is_dead() || (health.is_invulnerable() && !hit.ignore_invulnerability) {
+        return;
+    }
+
+    hit.damage *= health.multiplier(hit.damage_type);
+    if let Some((weak_point, xform)) = weak_point {
+        if weak_point.contains(xform, hit.point.xy()) {
+            hit.damage *= weak_point.multiplier;
+            hit.crit = true;
+        }
+    }
+    health.current -= hit.damage;
+    health.invulnerable_timer = health.invulnerability;
+    cmds.trigger_targets(OnDamaged(hit), e);
+
+    if health.is_dead() {
+        cmds.trigger_targets(OnDeath(hit), e);
+    }
+}
+
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
 pub struct Lifetime(Timer);
@@ -81,6 +280,10 @@ impl Lifetime {
     }
 }
 
+/// Triggered on an entity right before it's despawned for running out of time.
+#[derive(Event)]
+pub struct OnExpired;
+
 fn process_lifetimes(
     mut cmds: Commands,
     time: Res<Time>,
@@ -89,37 +292,12 @@ fn process_lifetimes(
     for (entity, mut lifetime) in q_lifetimes.iter_mut() {
         lifetime.0.tick(time.delta());
         if lifetime.0.finished() {
+            cmds.trigger_targets(OnExpired, entity);
             cmds.entity(entity).despawn_recursive();
         }
     }
 }
 
-pub trait RngSampler {
-    fn value(&mut self) -> f32;
-    fn value_one(&mut self) -> f32 {
-        (self.value() - 0.5) * 2.0
-    }
-    fn value_range(&mut self, min: f32, max: f32) -> f32 {
-        min + (max - min) * self.value()
-    }
-    fn circle(&mut self) -> Vec2 {
-        Vec2::new(self.value_one(), self.value_one()).normalize()
-    }
-    fn sphere(&mut self) -> Vec3 {
-        Vec3::new(self.value_one(), self.value_one(), self.value_one()).normalize()
-    }
-}
-
-#[derive(Default)]
-pub struct SimpleRng {
-    rng: ThreadRng,
-}
-impl RngSampler for SimpleRng {
-    fn value(&mut self) -> f32 {
-        self.rng.gen()
-    }
-}
-
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
 pub enum PickUp {
@@ -138,7 +316,7 @@ pub struct PickUpReceiver {
 }
 #[derive(Event)]
 pub struct OnPickedUp {
-    receiver_entity: Entity,
+    pub receiver_entity: Entity,
 }
 
 fn detect_pickups(
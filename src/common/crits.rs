@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use super::{GameRng, HitData, RngSampler, RngStream};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Crit>().register_type::<WeakPoint>();
+}
+
+/// Chance for the hits an entity deals to be critical.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct Crit {
+    /// Between 0 and 1.
+    pub chance: f32,
+    pub multiplier: f32,
+}
+impl Crit {
+    pub fn new(chance: f32, multiplier: f32) -> Self {
+        Self { chance, multiplier }
+    }
+
+    /// Rolls for a critical hit, multiplying its damage on success.
+    pub fn roll(&self, hit: &mut HitData, rng: &mut GameRng) {
+        if self.chance > 0.0 && rng.stream(RngStream::Crits).value() < self.chance {
+            hit.damage *= self.multiplier;
+            hit.crit = true;
+        }
+    }
+}
+
+/// Spot taking extra damage from hits landing on it, making them critical.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct WeakPoint {
+    /// Center in the entity's space.
+    pub offset: Vec2,
+    pub radius: f32,
+    pub multiplier: f32,
+}
+impl WeakPoint {
+    pub fn contains(&self, xform: &GlobalTransform, point: Vec2) -> bool {
+        let center = xform.transform_point(self.offset.extend(0.0)).xy();
+        center.distance_squared(point) <= self.radius * self.radius
+    }
+}
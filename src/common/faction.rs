@@ -0,0 +1,125 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rapier2d::prelude::*;
+
+pub const PLAYER_GROUP: Group = Group::GROUP_1;
+pub const ENEMY_GROUP: Group = Group::GROUP_2;
+pub const NEUTRAL_GROUP: Group = Group::GROUP_3;
+pub const ENVIRONMENT_GROUP: Group = Group::GROUP_4;
+pub const PROJECTILE_GROUP: Group = Group::GROUP_5;
+pub const PICKUP_GROUP: Group = Group::GROUP_6;
+
+/// Every group bodies of a faction belong to.
+const BODY_GROUPS: Group = PLAYER_GROUP
+    .union(ENEMY_GROUP)
+    .union(NEUTRAL_GROUP)
+    .union(ENVIRONMENT_GROUP);
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Faction>();
+    app.register_type::<DamageMatrix>();
+    app.init_resource::<DamageMatrix>();
+}
+
+/// Side an entity fights for, deciding what it collides with and who it can damage.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub enum Faction {
+    Player,
+    Enemy,
+    #[default]
+    Neutral,
+    Environment,
+}
+impl Faction {
+    pub fn group(self) -> Group {
+        match self {
+            Faction::Player => PLAYER_GROUP,
+            Faction::Enemy => ENEMY_GROUP,
+            Faction::Neutral => NEUTRAL_GROUP,
+            Faction::Environment => ENVIRONMENT_GROUP,
+        }
+    }
+}
+
+#[derive(Bundle, Default)]
+pub struct FactionBundle {
+    pub faction: Faction,
+    pub collision_groups: CollisionGroups,
+    pub solver_groups: SolverGroups,
+}
+impl FactionBundle {
+    /// Ships, asteroids and anything else solid. Bumps into other bodies and gets hit by projectiles.
+    pub fn body(faction: Faction) -> Self {
+        Self {
+            faction,
+            collision_groups: CollisionGroups::new(faction.group(), BODY_GROUPS | PROJECTILE_GROUP),
+            solver_groups: SolverGroups::new(faction.group(), BODY_GROUPS),
+        }
+    }
+
+    /// Touches bodies of any faction but never other projectiles or pickups, the
+    /// [`DamageMatrix`] decides which of them actually get hit.
+    pub fn projectile(faction: Faction) -> Self {
+        Self {
+            faction,
+            collision_groups: CollisionGroups::new(PROJECTILE_GROUP, BODY_GROUPS),
+            solver_groups: SolverGroups::new(PROJECTILE_GROUP, Group::NONE),
+        }
+    }
+}
+
+/// Which faction can damage which. Game modes toggle friendly fire here.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct DamageMatrix {
+    /// Indexed by attacker then target.
+    rules: [[bool; 4]; 4],
+}
+impl Default for DamageMatrix {
+    fn default() -> Self {
+        let mut matrix = Self {
+            rules: [[true; 4]; 4],
+        };
+        matrix.set_friendly_fire(false);
+        matrix
+    }
+}
+impl DamageMatrix {
+    pub fn can_damage(&self, attacker: Faction, target: Faction) -> bool {
+        self.rules[attacker as usize][target as usize]
+    }
+
+    pub fn set(&mut self, attacker: Faction, target: Faction, can_damage: bool) {
+        self.rules[attacker as usize][target as usize] = can_damage;
+    }
+
+    /// Lets players hurt players and enemies hurt enemies.
+    pub fn set_friendly_fire(&mut self, enabled: bool) {
+        self.set(Faction::Player, Faction::Player, enabled);
+        self.set(Faction::Enemy, Faction::Enemy, enabled);
+    }
+}
+
+/// Looks up the [`DamageMatrix`] for entities. Entities without a [`Faction`] can damage and be
+/// damaged by anyone.
+#[derive(SystemParam)]
+pub struct Factions<'w, 's> {
+    q_factions: Query<'w, 's, &'static Faction>,
+    r_matrix: Res<'w, DamageMatrix>,
+}
+impl Factions<'_, '_> {
+    pub fn get(&self, entity: Entity) -> Option<Faction> {
+        self.q_factions.get(entity).ok().copied()
+    }
+
+    pub fn can_damage(&self, attacker: Entity, target: Entity) -> bool {
+        self.faction_can_damage(self.get(attacker), target)
+    }
+
+    pub fn faction_can_damage(&self, attacker: Option<Faction>, target: Entity) -> bool {
+        match (attacker, self.get(target)) {
+            (Some(attacker), Some(target)) => self.r_matrix.can_damage(attacker, target),
+            _ => true,
+        }
+    }
+}
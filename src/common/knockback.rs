@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::{GameplaySet, OnHit};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Knockback>();
+
+    app.observe(knockback_on_hit);
+    // After everything that hits or pushes, before physics picks the impulses up.
+    app.add_systems(
+        FixedUpdate,
+        apply_knockback
+            .after(GameplaySet)
+            .before(PhysicsSet::SyncBackend),
+    );
+}
+
+/// Pushes the entity back when hit. Every push of a step adds up into one impulse.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Knockback {
+    /// Speed given per point of damage, before mass is accounted for.
+    pub speed_per_damage: f32,
+    /// Speed given by every hit, whatever its damage.
+    pub speed_per_hit: f32,
+    /// Fraction of hit knockback ignored, 1 makes it immovable by hits.
+    pub resistance: f32,
+    /// 1 lets heavier bodies move proportionally less, 0 gives every mass the same speed.
+    /// Needs [`ReadMassProperties`] to be anything other than 1.
+    pub mass_scaling: f32,
+    pending: Vec2,
+}
+impl Default for Knockback {
+    fn default() -> Self {
+        Self::new(10.0)
+    }
+}
+impl Knockback {
+    pub fn new(speed_per_damage: f32) -> Self {
+        Self {
+            speed_per_damage,
+            speed_per_hit: 0.0,
+            resistance: 0.0,
+            mass_scaling: 1.0,
+            pending: Vec2::ZERO,
+        }
+    }
+
+    /// Same push for every hit, however hard.
+    pub fn per_hit(speed: f32) -> Self {
+        Self {
+            speed_per_hit: speed,
+            ..Self::new(0.0)
+        }
+    }
+
+    pub fn with_resistance(mut self, resistance: f32) -> Self {
+        self.resistance = resistance;
+        self
+    }
+
+    pub fn with_mass_scaling(mut self, mass_scaling: f32) -> Self {
+        self.mass_scaling = mass_scaling;
+        self
+    }
+
+    /// Queues a raw impulse for this step, ignoring resistance.
+    pub fn push(&mut self, impulse: Vec2) {
+        self.pending += impulse;
+    }
+}
+
+fn knockback_on_hit(
+    e_hit: Trigger<OnHit>,
+    mut cmds: Commands,
+    mut q_knockbacks: Query<&mut Knockback>,
+) {
+    let hit = &e_hit.event().0;
+    let Ok(mut knockback) = q_knockbacks.get_mut(e_hit.entity()) else {
+        // Blasts still push bodies that don't react to hits. The hit could have killed it.
+        if let Some(impulse) = hit.impulse {
+            cmds.entity(e_hit.entity()).try_insert(ExternalImpulse {
+                impulse: hit.dir.xy() * impulse,
+                ..default()
+            });
+        }
+        return;
+    };
+
+    let strength = hit
+        .impulse
+        .unwrap_or(hit.damage * knockback.speed_per_damage + knockback.speed_per_hit);
+    let impulse = hit.dir.xy() * (strength * (1.0 - knockback.resistance)).max(0.0);
+    knockback.push(impulse);
+}
+
+fn apply_knockback(
+    mut cmds: Commands,
+    mut q_knockbacks: Query<(
+        Entity,
+        &mut Knockback,
+        Option<&ReadMassProperties>,
+        Option<&mut ExternalImpulse>,
+    )>,
+) {
+    for (e, mut knockback, mass, impulse) in q_knockbacks.iter_mut() {
+        if knockback.pending == Vec2::ZERO {
+            continue;
+        }
+
+        // Impulses already scale speed down with mass, make up for the part that shouldn't.
+        let mass = mass.map_or(1.0, |mass| mass.mass.max(f32::EPSILON));
+        let pending =
+            std::mem::take(&mut knockback.pending) * mass.powf(1.0 - knockback.mass_scaling);
+        match impulse {
+            Some(mut impulse) => impulse.impulse += pending,
+            None => {
+                cmds.entity(e).insert(ExternalImpulse {
+                    impulse: pending,
+                    ..default()
+                });
+            }
+        }
+    }
+}
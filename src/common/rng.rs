@@ -0,0 +1,273 @@
+use std::f32::consts::{SQRT_2, TAU};
+
+use bevy::{prelude::*, utils::HashMap};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+pub fn plugin(app: &mut App) {
+    let rng = GameRng::from_args();
+    info!("Game seed: {}", rng.seed());
+    app.insert_resource(rng);
+}
+
+pub trait RngSampler {
+    fn value(&mut self) -> f32;
+    fn value_one(&mut self) -> f32 {
+        (self.value() - 0.5) * 2.0
+    }
+    fn value_range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.value()
+    }
+    /// Uniformly distributed unit direction.
+    fn circle(&mut self) -> Vec2 {
+        Vec2::from_angle(self.value_range(0.0, TAU))
+    }
+    /// Uniformly distributed unit direction in 3D.
+    fn sphere(&mut self) -> Vec3 {
+        let z = self.value_one();
+        let xy = Vec2::from_angle(self.value_range(0.0, TAU)) * (1.0 - z * z).sqrt();
+        xy.extend(z)
+    }
+    /// Point uniformly distributed over the area of a ring.
+    fn annulus(&mut self, inner_radius: f32, outer_radius: f32) -> Vec2 {
+        let radius = self
+            .value_range(inner_radius * inner_radius, outer_radius * outer_radius)
+            .sqrt();
+        self.circle() * radius
+    }
+    /// Point uniformly distributed over the area of a disc.
+    fn disc(&mut self, radius: f32) -> Vec2 {
+        self.annulus(0.0, radius)
+    }
+    /// Unit direction at most `half_angle` radians away from `dir`.
+    fn arc(&mut self, dir: Vec2, half_angle: f32) -> Vec2 {
+        let dir = dir.try_normalize().unwrap_or(Vec2::Y);
+        Vec2::from_angle(self.value_range(-half_angle, half_angle)).rotate(dir)
+    }
+    /// Point uniformly distributed over a circle sector around `dir`, between `min_distance` and `max_distance`.
+    fn cone(&mut self, dir: Vec2, half_angle: f32, min_distance: f32, max_distance: f32) -> Vec2 {
+        let distance = self
+            .value_range(min_distance * min_distance, max_distance * max_distance)
+            .sqrt();
+        self.arc(dir, half_angle) * distance
+    }
+    /// Index picked with probability proportional to its weight. `None` if no weight is positive.
+    fn weighted_choice(&mut self, weights: &[f32]) -> Option<usize> {
+        let total: f32 = weights.iter().filter(|w| **w > 0.0).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut pick = self.value() * total;
+        let mut last = None;
+        for (i, weight) in weights.iter().enumerate().filter(|(_, w)| **w > 0.0) {
+            if pick < *weight {
+                return Some(i);
+            }
+            pick -= weight;
+            last = Some(i);
+        }
+        last
+    }
+    /// Points inside a disc of `radius` where no two are closer than `min_distance`.
+    /// Uses Bridson's algorithm, so the result is tightly packed but without visible patterns.
+    fn poisson_disc(&mut self, radius: f32, min_distance: f32) -> Vec<Vec2> {
+        const ATTEMPTS: usize = 30;
+
+        if radius <= 0.0 || min_distance <= 0.0 {
+            return vec![Vec2::ZERO];
+        }
+
+        let cell_size = min_distance / SQRT_2;
+        let grid_size = (radius * 2.0 / cell_size).ceil() as usize + 1;
+        let cell_of = |p: Vec2| {
+            let cell = ((p + Vec2::splat(radius)) / cell_size).floor();
+            (cell.x as usize, cell.y as usize)
+        };
+
+        let mut grid: Vec<Option<usize>> = vec![None; grid_size * grid_size];
+        let mut points = vec![self.disc(radius)];
+        let mut active = vec![0];
+        let (x, y) = cell_of(points[0]);
+        grid[y * grid_size + x] = Some(0);
+
+        while !active.is_empty() {
+            let active_index =
+                ((self.value() * active.len() as f32) as usize).min(active.len() - 1);
+            let origin = points[active[active_index]];
+
+            let mut found = false;
+            for _ in 0..ATTEMPTS {
+                let candidate = origin + self.annulus(min_distance, min_distance * 2.0);
+                if candidate.length() > radius {
+                    continue;
+                }
+
+                let (cx, cy) = cell_of(candidate);
+                let too_close = (cy.saturating_sub(2)..(cy + 3).min(grid_size)).any(|y| {
+                    (cx.saturating_sub(2)..(cx + 3).min(grid_size)).any(|x| {
+                        grid[y * grid_size + x].is_some_and(|i| {
+                            points[i].distance_squared(candidate) < min_distance * min_distance
+                        })
+                    })
+                });
+                if too_close {
+                    continue;
+                }
+
+                grid[cy * grid_size + cx] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+                found = true;
+                break;
+            }
+
+            if !found {
+                active.swap_remove(active_index);
+            }
+        }
+        points
+    }
+}
+
+/// Deterministic generator, the same seed always yields the same sequence.
+pub struct SeededRng {
+    rng: StdRng,
+}
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+impl RngSampler for SeededRng {
+    fn value(&mut self) -> f32 {
+        self.rng.gen()
+    }
+}
+
+/// Independent random streams, so rolling more numbers in one subsystem
+/// doesn't change the results of another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    General,
+    Spawner,
+    Money,
+    Crits,
+    Enemies,
+    Steering,
+    Asteroids,
+}
+
+/// Seedable source of randomness for gameplay. Pass `--seed <u64>` on the
+/// command line to reproduce a previous run.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<RngStream, SeededRng>,
+}
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::default(),
+        }
+    }
+
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// Uses the `--seed` command line argument if present, random otherwise.
+    pub fn from_args() -> Self {
+        let mut args = std::env::args().skip_while(|arg| arg != "--seed").skip(1);
+        match args.next().map(|seed| seed.parse::<u64>()) {
+            Some(Ok(seed)) => Self::new(seed),
+            Some(Err(err)) => {
+                warn!("Invalid --seed argument: {err}");
+                Self::from_entropy()
+            }
+            None => Self::from_entropy(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts every stream from the current seed.
+    pub fn reset(&mut self) {
+        self.streams.clear();
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.reset();
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut SeededRng {
+        let seed = self.seed;
+        self.streams
+            .entry(stream)
+            .or_insert_with(|| SeededRng::new(fork_seed(seed, stream)))
+    }
+}
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+impl RngSampler for GameRng {
+    fn value(&mut self) -> f32 {
+        self.stream(RngStream::General).value()
+    }
+}
+
+fn fork_seed(seed: u64, stream: RngStream) -> u64 {
+    // SplitMix64 step so consecutive stream ids end up far apart.
+    let mut z = seed ^ (stream as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poisson_disc_respects_min_distance() {
+        let mut rng = SeededRng::new(7);
+        let (radius, min_distance) = (500.0, 40.0);
+        let points = rng.poisson_disc(radius, min_distance);
+
+        assert!(points.len() > 1);
+        for (i, a) in points.iter().enumerate() {
+            assert!(a.length() <= radius + 0.001);
+            for b in &points[i + 1..] {
+                assert!(a.distance(*b) >= min_distance - 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_choice_skips_zero_weights() {
+        let mut rng = SeededRng::new(7);
+        let weights = [0.0, 1.0, 0.0, 3.0, -2.0];
+        for _ in 0..10_000 {
+            let choice = rng.weighted_choice(&weights).unwrap();
+            assert!(choice == 1 || choice == 3);
+        }
+        assert_eq!(rng.weighted_choice(&[0.0, 0.0]), None);
+    }
+
+    #[test]
+    fn annulus_stays_between_radii() {
+        let mut rng = SeededRng::new(7);
+        let (inner, outer) = (100.0, 250.0);
+        for _ in 0..10_000 {
+            let distance = rng.annulus(inner, outer).length();
+            assert!((inner - 0.001..=outer + 0.001).contains(&distance));
+        }
+    }
+}
@@ -0,0 +1,607 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::{Faction, GameRng, GameplaySet, RngSampler, RngStream};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Steering>()
+        .register_type::<Seek>()
+        .register_type::<Flee>()
+        .register_type::<Arrive>()
+        .register_type::<Pursue>()
+        .register_type::<Evade>()
+        .register_type::<Wander>()
+        .register_type::<AvoidObstacles>()
+        .register_type::<Separation>()
+        .register_type::<Flock>();
+
+    app.configure_sets(FixedUpdate, SteeringSet.in_set(GameplaySet));
+    // Chained so the blend adds up in the same order every step.
+    app.add_systems(
+        FixedUpdate,
+        (
+            steer_seek,
+            steer_flee,
+            steer_arrive,
+            steer_pursue,
+            steer_evade,
+            steer_wander,
+            steer_separation,
+            steer_flock,
+            avoid_obstacles,
+            apply_steering,
+        )
+            .chain()
+            .in_set(SteeringSet),
+    );
+}
+
+/// Behaviours blending into [`Steering`], followed by the velocity or force update.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SteeringSet;
+
+/// Moves the entity according to the weighted blend of its steering behaviours, like [`Seek`]
+/// or [`Wander`]. Each behaviour asks for a velocity, the agent accelerates towards their average.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Steering {
+    pub max_speed: f32,
+    /// Largest change in speed per second.
+    pub max_accel: f32,
+    /// Radians per second the heading can change. Turning agents keep their speed and only
+    /// rotate their velocity, like missiles. Always changes [`Velocity`] directly.
+    pub turn_rate: Option<f32>,
+    pub output: SteeringOutput,
+    desired: Vec2,
+    weight: f32,
+}
+impl Steering {
+    pub fn new(max_speed: f32, max_accel: f32) -> Self {
+        Self {
+            max_speed,
+            max_accel,
+            turn_rate: None,
+            output: SteeringOutput::Velocity,
+            desired: Vec2::ZERO,
+            weight: 0.0,
+        }
+    }
+
+    pub fn turning(max_speed: f32, turn_rate: f32) -> Self {
+        Self {
+            turn_rate: Some(turn_rate),
+            ..Self::new(max_speed, 0.0)
+        }
+    }
+
+    pub fn with_output(mut self, output: SteeringOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Blends in a velocity the agent would like to move at this step.
+    pub fn add(&mut self, desired: Vec2, weight: f32) {
+        if weight > 0.0 {
+            self.desired += desired * weight;
+            self.weight += weight;
+        }
+    }
+
+    /// Weighted average of the velocities asked for this step, if any, and starts over.
+    fn take_desired(&mut self) -> Option<Vec2> {
+        let weight = std::mem::take(&mut self.weight);
+        let desired = std::mem::take(&mut self.desired);
+        (weight > 0.0).then(|| desired / weight)
+    }
+}
+
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteeringOutput {
+    /// Changes [`Velocity`] directly.
+    #[default]
+    Velocity,
+    /// Drives the [`ExternalForce`] so collisions and impulses still play out on their own.
+    /// Needs [`ReadMassProperties`] to account for mass.
+    Force,
+}
+
+/// Something to steer relative to.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub enum SteeringTarget {
+    Entity(Entity),
+    Point(Vec2),
+}
+impl SteeringTarget {
+    pub fn position(self, q_xforms: &Query<&GlobalTransform>) -> Option<Vec2> {
+        match self {
+            SteeringTarget::Entity(e) => q_xforms.get(e).ok().map(|x| x.translation().xy()),
+            SteeringTarget::Point(point) => Some(point),
+        }
+    }
+}
+
+/// Full speed straight at `target`.
+pub fn seek(position: Vec2, target: Vec2, max_speed: f32) -> Vec2 {
+    (target - position).normalize_or_zero() * max_speed
+}
+
+/// Full speed away from `threat`, as long as it's within `panic_radius`.
+pub fn flee(position: Vec2, threat: Vec2, max_speed: f32, panic_radius: f32) -> Option<Vec2> {
+    let away = position - threat;
+    (away.length_squared() < panic_radius * panic_radius)
+        .then(|| away.normalize_or_zero() * max_speed)
+}
+
+/// Like [`seek`], but slows down within `slow_radius` to come to a stop on the target.
+pub fn arrive(position: Vec2, target: Vec2, max_speed: f32, slow_radius: f32) -> Vec2 {
+    let to_target = target - position;
+    let speed = if slow_radius > 0.0 {
+        max_speed * (to_target.length() / slow_radius).min(1.0)
+    } else {
+        max_speed
+    };
+    to_target.normalize_or_zero() * speed
+}
+
+/// Where a moving target will be by the time the agent gets there, looking at most
+/// `max_prediction` seconds ahead.
+pub fn predict(
+    position: Vec2,
+    max_speed: f32,
+    target: Vec2,
+    target_velocity: Vec2,
+    max_prediction: f32,
+) -> Vec2 {
+    let time = if max_speed > 0.0 {
+        (position.distance(target) / max_speed).min(max_prediction)
+    } else {
+        max_prediction
+    };
+    target + target_velocity * time
+}
+
+/// Heads straight for the target.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Seek {
+    pub target: Option<SteeringTarget>,
+    pub weight: f32,
+}
+impl Default for Seek {
+    fn default() -> Self {
+        Self {
+            target: None,
+            weight: 1.0,
+        }
+    }
+}
+impl Seek {
+    pub fn new(target: SteeringTarget) -> Self {
+        Self {
+            target: Some(target),
+            ..default()
+        }
+    }
+}
+
+/// Runs from the target while it's close.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Flee {
+    pub target: Option<SteeringTarget>,
+    pub panic_radius: f32,
+    pub weight: f32,
+}
+impl Flee {
+    pub fn new(target: SteeringTarget, panic_radius: f32) -> Self {
+        Self {
+            target: Some(target),
+            panic_radius,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Heads for the target and stops on it.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Arrive {
+    pub target: Option<SteeringTarget>,
+    /// Distance from the target at which the agent starts slowing down.
+    pub slow_radius: f32,
+    pub weight: f32,
+}
+impl Arrive {
+    pub fn new(target: SteeringTarget, slow_radius: f32) -> Self {
+        Self {
+            target: Some(target),
+            slow_radius,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Heads for where a moving target is going to be.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Pursue {
+    pub target: Option<Entity>,
+    /// Seconds ahead the target's position can be predicted.
+    pub max_prediction: f32,
+    pub weight: f32,
+}
+impl Pursue {
+    pub fn new(target: Entity, max_prediction: f32) -> Self {
+        Self {
+            target: Some(target),
+            max_prediction,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Runs from where a moving target is going to be while it's close.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Evade {
+    pub target: Option<Entity>,
+    pub panic_radius: f32,
+    /// Seconds ahead the target's position can be predicted.
+    pub max_prediction: f32,
+    pub weight: f32,
+}
+impl Evade {
+    pub fn new(target: Entity, panic_radius: f32, max_prediction: f32) -> Self {
+        Self {
+            target: Some(target),
+            panic_radius,
+            max_prediction,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Meanders around by heading for a point that drifts along a circle in front of the agent.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Wander {
+    /// How far ahead of the agent the circle is.
+    pub distance: f32,
+    pub radius: f32,
+    /// Radians per second the point can drift along the circle.
+    pub jitter: f32,
+    pub angle: f32,
+    pub weight: f32,
+}
+impl Wander {
+    pub fn new(distance: f32, radius: f32, jitter: f32) -> Self {
+        Self {
+            distance,
+            radius,
+            jitter,
+            angle: 0.0,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Veers off colliders in the way, found by sweeping a circle along the velocity.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct AvoidObstacles {
+    /// Seconds of travel ahead obstacles are looked for.
+    pub look_ahead: f32,
+    /// Radius of the swept circle, usually about the size of the agent.
+    pub radius: f32,
+    /// Collision groups of the colliders to avoid.
+    #[reflect(ignore)]
+    pub groups: Group,
+    pub weight: f32,
+}
+impl AvoidObstacles {
+    pub fn new(look_ahead: f32, radius: f32, groups: Group) -> Self {
+        Self {
+            look_ahead,
+            radius,
+            groups,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Keeps away from other agents with [`Separation`], the closer they are the harder.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Separation {
+    pub radius: f32,
+    pub weight: f32,
+}
+impl Separation {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Moves along with nearby agents of the same [`Faction`] that also flock.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Flock {
+    pub radius: f32,
+    /// Weight of matching the heading of the flock.
+    pub alignment: f32,
+    /// Weight of heading for the center of the flock.
+    pub cohesion: f32,
+}
+impl Flock {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            alignment: 1.0,
+            cohesion: 1.0,
+        }
+    }
+}
+
+fn steer_seek(
+    mut q_agents: Query<(&mut Steering, &GlobalTransform, &Seek)>,
+    q_xforms: Query<&GlobalTransform>,
+) {
+    for (mut steering, xform, behaviour) in q_agents.iter_mut() {
+        let Some(target) = behaviour.target.and_then(|t| t.position(&q_xforms)) else {
+            continue;
+        };
+        let desired = seek(xform.translation().xy(), target, steering.max_speed);
+        steering.add(desired, behaviour.weight);
+    }
+}
+
+fn steer_flee(
+    mut q_agents: Query<(&mut Steering, &GlobalTransform, &Flee)>,
+    q_xforms: Query<&GlobalTransform>,
+) {
+    for (mut steering, xform, behaviour) in q_agents.iter_mut() {
+        let Some(threat) = behaviour.target.and_then(|t| t.position(&q_xforms)) else {
+            continue;
+        };
+        let position = xform.translation().xy();
+        if let Some(desired) = flee(position, threat, steering.max_speed, behaviour.panic_radius) {
+            steering.add(desired, behaviour.weight);
+        }
+    }
+}
+
+fn steer_arrive(
+    mut q_agents: Query<(&mut Steering, &GlobalTransform, &Arrive)>,
+    q_xforms: Query<&GlobalTransform>,
+) {
+    for (mut steering, xform, behaviour) in q_agents.iter_mut() {
+        let Some(target) = behaviour.target.and_then(|t| t.position(&q_xforms)) else {
+            continue;
+        };
+        let position = xform.translation().xy();
+        let desired = arrive(position, target, steering.max_speed, behaviour.slow_radius);
+        steering.add(desired, behaviour.weight);
+    }
+}
+
+fn steer_pursue(
+    mut q_agents: Query<(&mut Steering, &GlobalTransform, &Pursue)>,
+    q_targets: Query<(&GlobalTransform, Option<&Velocity>)>,
+) {
+    for (mut steering, xform, behaviour) in q_agents.iter_mut() {
+        let Some((target_xform, target_velocity)) =
+            behaviour.target.and_then(|e| q_targets.get(e).ok())
+        else {
+            continue;
+        };
+        let position = xform.translation().xy();
+        let target = predict(
+            position,
+            steering.max_speed,
+            target_xform.translation().xy(),
+            target_velocity.map_or(Vec2::ZERO, |v| v.linvel),
+            behaviour.max_prediction,
+        );
+        let desired = seek(position, target, steering.max_speed);
+        steering.add(desired, behaviour.weight);
+    }
+}
+
+fn steer_evade(
+    mut q_agents: Query<(&mut Steering, &GlobalTransform, &Evade)>,
+    q_targets: Query<(&GlobalTransform, Option<&Velocity>)>,
+) {
+    for (mut steering, xform, behaviour) in q_agents.iter_mut() {
+        let Some((target_xform, target_velocity)) =
+            behaviour.target.and_then(|e| q_targets.get(e).ok())
+        else {
+            continue;
+        };
+        let position = xform.translation().xy();
+        let threat = predict(
+            position,
+            steering.max_speed,
+            target_xform.translation().xy(),
+            target_velocity.map_or(Vec2::ZERO, |v| v.linvel),
+            behaviour.max_prediction,
+        );
+        if let Some(desired) = flee(position, threat, steering.max_speed, behaviour.panic_radius) {
+            steering.add(desired, behaviour.weight);
+        }
+    }
+}
+
+fn steer_wander(
+    r_time: Res<Time>,
+    mut r_rng: ResMut<GameRng>,
+    mut q_agents: Query<(&mut Steering, &mut Wander, &GlobalTransform, &Velocity)>,
+) {
+    let rng = r_rng.stream(RngStream::Steering);
+    for (mut steering, mut wander, xform, velocity) in q_agents.iter_mut() {
+        wander.angle += rng.value_one() * wander.jitter * r_time.delta_seconds();
+
+        let heading = velocity.linvel.normalize_or(xform.up().xy());
+        let target = heading * wander.distance
+            + heading.rotate(Vec2::from_angle(wander.angle)) * wander.radius;
+        let desired = target.normalize_or_zero() * steering.max_speed;
+        steering.add(desired, wander.weight);
+    }
+}
+
+fn steer_separation(
+    mut q_agents: Query<(Entity, &mut Steering, &GlobalTransform, &Separation)>,
+    q_neighbours: Query<(Entity, &GlobalTransform), With<Separation>>,
+) {
+    for (e, mut steering, xform, separation) in q_agents.iter_mut() {
+        let position = xform.translation().xy();
+        let push: Vec2 = q_neighbours
+            .iter()
+            .filter(|(neighbour, _)| *neighbour != e)
+            .map(|(_, neighbour_xform)| position - neighbour_xform.translation().xy())
+            .filter(|away| away.length() < separation.radius)
+            .map(|away| away.normalize_or_zero() * (1.0 - away.length() / separation.radius))
+            .sum();
+        if push != Vec2::ZERO {
+            let desired = push.normalize() * steering.max_speed;
+            steering.add(desired, separation.weight * push.length().min(1.0));
+        }
+    }
+}
+
+fn steer_flock(
+    mut q_agents: Query<(
+        Entity,
+        &mut Steering,
+        &GlobalTransform,
+        &Flock,
+        Option<&Faction>,
+    )>,
+    q_neighbours: Query<(Entity, &GlobalTransform, &Velocity, Option<&Faction>), With<Flock>>,
+) {
+    for (e, mut steering, xform, flock, faction) in q_agents.iter_mut() {
+        let position = xform.translation().xy();
+        let mut count = 0;
+        let mut center = Vec2::ZERO;
+        let mut heading = Vec2::ZERO;
+        for (_, neighbour_xform, neighbour_velocity, _) in
+            q_neighbours
+                .iter()
+                .filter(|(neighbour, _, _, neighbour_faction)| {
+                    *neighbour != e && *neighbour_faction == faction
+                })
+        {
+            let neighbour_position = neighbour_xform.translation().xy();
+            if position.distance(neighbour_position) < flock.radius {
+                count += 1;
+                center += neighbour_position;
+                heading += neighbour_velocity.linvel;
+            }
+        }
+        if count == 0 {
+            continue;
+        }
+
+        let alignment = heading.normalize_or_zero() * steering.max_speed;
+        let cohesion = seek(position, center / count as f32, steering.max_speed);
+        steering.add(alignment, flock.alignment);
+        steering.add(cohesion, flock.cohesion);
+    }
+}
+
+fn avoid_obstacles(
+    mut q_agents: Query<(
+        Entity,
+        &mut Steering,
+        &GlobalTransform,
+        &Velocity,
+        &AvoidObstacles,
+    )>,
+    q_obstacles: Query<&GlobalTransform>,
+    r_rapier: Res<RapierContext>,
+) {
+    for (e, mut steering, xform, velocity, avoid) in q_agents.iter_mut() {
+        let Some(heading) = velocity.linvel.try_normalize() else {
+            continue;
+        };
+        let look_ahead = velocity.linvel.length() * avoid.look_ahead;
+        if look_ahead <= 0.0 {
+            continue;
+        }
+        let position = xform.translation().xy();
+        let Some((obstacle, hit)) = r_rapier.cast_shape(
+            position,
+            0.0,
+            heading,
+            &Collider::ball(avoid.radius),
+            ShapeCastOptions::with_max_time_of_impact(look_ahead),
+            QueryFilter::default()
+                .exclude_sensors()
+                .exclude_collider(e)
+                .exclude_rigid_body(e)
+                .groups(CollisionGroups::new(Group::ALL, avoid.groups)),
+        ) else {
+            continue;
+        };
+        let Ok(obstacle_xform) = q_obstacles.get(obstacle) else {
+            continue;
+        };
+
+        // Veer off to whichever side of the obstacle the agent is going to pass on.
+        let away = position + heading * hit.time_of_impact - obstacle_xform.translation().xy();
+        let sideways = (away - heading * away.dot(heading)).normalize_or(heading.perp());
+        let urgency = 1.0 - hit.time_of_impact / look_ahead;
+        let desired = sideways * steering.max_speed;
+        steering.add(desired, avoid.weight * urgency);
+    }
+}
+
+fn apply_steering(
+    r_time: Res<Time>,
+    mut q_agents: Query<(
+        &mut Steering,
+        &mut Velocity,
+        Option<&ReadMassProperties>,
+        Option<&mut ExternalForce>,
+    )>,
+) {
+    let dt = r_time.delta_seconds();
+    if dt <= 0.0 {
+        return;
+    }
+
+    for (mut steering, mut velocity, mass, external_force) in q_agents.iter_mut() {
+        let desired = steering.take_desired();
+
+        if let Some(turn_rate) = steering.turn_rate {
+            let Some(desired) = desired.filter(|d| *d != Vec2::ZERO) else {
+                continue;
+            };
+            if velocity.linvel == Vec2::ZERO {
+                continue;
+            }
+            let max_turn = turn_rate * dt;
+            let turn = velocity
+                .linvel
+                .angle_between(desired)
+                .clamp(-max_turn, max_turn);
+            velocity.linvel = Vec2::from_angle(turn).rotate(velocity.linvel);
+            continue;
+        }
+
+        // Whatever it takes to match the desired velocity this step, within what the agent can do.
+        let accel = desired.map_or(Vec2::ZERO, |desired| {
+            ((desired - velocity.linvel) / dt).clamp_length_max(steering.max_accel)
+        });
+        match steering.output {
+            SteeringOutput::Velocity => velocity.linvel += accel * dt,
+            SteeringOutput::Force => {
+                if let Some(mut external_force) = external_force {
+                    external_force.force = accel * mass.map_or(1.0, |mass| mass.mass);
+                }
+            }
+        }
+    }
+}
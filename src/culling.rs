@@ -0,0 +1,57 @@
+use crate::{prelude::*, projectiles::Projectile};
+
+/// Distance past the edges of the view things are left alone within.
+pub const CULL_MARGIN: f32 = 2000.0;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<CullMetrics>();
+    app.init_resource::<CullMetrics>();
+
+    app.add_systems(OnEnter(GameStates::Match), reset_cull_metrics);
+    app.add_systems(FixedUpdate, cull_offscreen.in_set(GameplaySet));
+}
+
+/// Entities despawned for being far outside every camera's view this match.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct CullMetrics {
+    pub projectiles: u32,
+    pub pickups: u32,
+}
+
+fn reset_cull_metrics(mut r_metrics: ResMut<CullMetrics>) {
+    *r_metrics = default();
+}
+
+fn cull_offscreen(
+    mut cmds: Commands,
+    r_viewport: Res<VirtualViewport>,
+    mut r_metrics: ResMut<CullMetrics>,
+    q_cameras: Query<(&GlobalTransform, &OrthographicProjection), With<PlayerCamera>>,
+    q_projectiles: Query<(Entity, &GlobalTransform), With<Projectile>>,
+    q_pickups: Query<(Entity, &GlobalTransform), With<PickUp>>,
+) {
+    if q_cameras.is_empty() {
+        return;
+    }
+
+    let offscreen = |xform: &GlobalTransform| {
+        let position = xform.translation().xy();
+        !q_cameras.iter().any(|(camera_xform, projection)| {
+            let half_extents = r_viewport.size / 2.0 * projection.scale + CULL_MARGIN;
+            let offset = (position - camera_xform.translation().xy()).abs();
+            offset.x <= half_extents.x && offset.y <= half_extents.y
+        })
+    };
+
+    for (e, _) in q_projectiles.iter().filter(|(_, xform)| offscreen(xform)) {
+        r_metrics.projectiles += 1;
+        // Same as running out of time or range, so rockets still go off.
+        cmds.trigger_targets(OnExpired, e);
+        cmds.entity(e).despawn_recursive();
+    }
+    for (e, _) in q_pickups.iter().filter(|(_, xform)| offscreen(xform)) {
+        r_metrics.pickups += 1;
+        cmds.entity(e).despawn_recursive();
+    }
+}
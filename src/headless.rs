@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use bevy::{
+    app::ScheduleRunnerPlugin, input::InputPlugin, log::LogPlugin, scene::ScenePlugin,
+    state::app::StatesPlugin, time::TimeUpdateStrategy,
+};
+
+use crate::{
+    culling::CullMetrics,
+    prelude::*,
+    score::{PlayerCurrency, PlayerWallets},
+    FIXED_TIMESTEP_HZ,
+};
+
+/// Engine setup to simulate matches without a window or GPU.
+pub fn plugin(app: &mut App) {
+    app.add_plugins((
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)),
+        LogPlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+        InputPlugin,
+        StatesPlugin,
+        AssetPlugin::default(),
+        ScenePlugin,
+    ));
+
+    // Simulate as fast as possible, one fixed step per frame.
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1.0 / FIXED_TIMESTEP_HZ,
+    )));
+
+    // Assets usually registered by the render plugins that gameplay still spawns.
+    app.init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .init_asset::<Image>();
+}
+
+/// Starts a match right away and exits the app as soon as it's over.
+pub fn single_match(app: &mut App) {
+    app.add_systems(Startup, start_match);
+    app.add_systems(OnExit(GameStates::Match), exit_on_match_end);
+}
+
+fn start_match(mut r_state: ResMut<NextState<GameStates>>) {
+    r_state.set(GameStates::Match);
+}
+
+fn exit_on_match_end(
+    r_currency: Res<PlayerCurrency>,
+    r_wallets: Res<PlayerWallets>,
+    r_culled: Res<CullMetrics>,
+    r_time: Res<Time<Fixed>>,
+    mut e_exit: EventWriter<AppExit>,
+) {
+    info!(
+        "Match finished after {:.2}s with {} currency",
+        r_time.elapsed_seconds(),
+        r_currency.0 + r_wallets.total()
+    );
+    info!(
+        "Culled {} projectiles and {} pickups off screen",
+        r_culled.projectiles, r_culled.pickups
+    );
+    e_exit.send(AppExit::Success);
+}
@@ -0,0 +1,135 @@
+pub mod actions;
+pub mod gamepad;
+pub mod local_players;
+pub use actions::*;
+pub use gamepad::*;
+pub use local_players::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_plugins((actions::plugin, gamepad::plugin, local_players::plugin));
+
+    app.register_type::<PlayerInputs>();
+    app.init_resource::<PlayerInputs>();
+
+    app.configure_sets(FixedPreUpdate, (InputSet::Read, InputSet::Override).chain());
+    app.add_systems(
+        FixedPreUpdate,
+        (read_player_input, clear_mouse_wheel)
+            .chain()
+            .in_set(InputSet::Read),
+    );
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputSet {
+    /// Samples the devices into [`PlayerInputs`].
+    Read,
+    /// Replaces the sampled input, e.g. with a replay.
+    Override,
+}
+
+/// Input of every local player for the current fixed step, indexed by player. Gameplay reads
+/// this instead of the devices so it can be recorded and fed back.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct PlayerInputs(pub Vec<PlayerInput>);
+impl PlayerInputs {
+    pub fn get(&self, index: usize) -> PlayerInput {
+        self.0.get(index).copied().unwrap_or_default()
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerInput {
+    pub movement: Vec2,
+    pub aim: Option<Aim>,
+    pub fire: bool,
+    #[serde(default)]
+    pub dash: bool,
+    #[serde(default)]
+    pub next_weapon: bool,
+    #[serde(default)]
+    pub previous_weapon: bool,
+    /// Weapon slot picked directly, e.g. with the number keys.
+    #[serde(default)]
+    pub weapon_slot: Option<usize>,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Aim {
+    /// Point in world space, e.g. the cursor.
+    Point(Vec2),
+    /// Direction relative to the ship, e.g. a stick.
+    Direction(Vec2),
+}
+impl Aim {
+    pub fn direction_from(&self, origin: Vec2) -> Vec2 {
+        match self {
+            Aim::Point(point) => *point - origin,
+            Aim::Direction(dir) => *dir,
+        }
+    }
+}
+
+fn read_player_input(
+    mut r_inputs: ResMut<PlayerInputs>,
+    r_players: Res<LocalPlayers>,
+    r_map: Res<ActionMap>,
+    r_pending: Res<PendingRebind>,
+    r_sticks: Res<GamepadSticks>,
+    r_last_device: Res<LastInputDevice>,
+    devices: InputDevices,
+) {
+    r_inputs.0.clear();
+    for device in r_players.0.iter() {
+        // Whatever is pressed while rebinding is meant for the new binding, not the game.
+        let pressed = |action| r_pending.0.is_none() && r_map.pressed(action, &devices, device);
+        let sticks = r_sticks.for_device(device);
+
+        let mut movement = Vec2::ZERO;
+        if pressed(InputAction::MoveUp) {
+            movement += Vec2::Y;
+        }
+        if pressed(InputAction::MoveDown) {
+            movement -= Vec2::Y;
+        }
+        if pressed(InputAction::MoveRight) {
+            movement += Vec2::X;
+        }
+        if pressed(InputAction::MoveLeft) {
+            movement -= Vec2::X;
+        }
+        movement = (movement + sticks.left).clamp_length_max(1.0);
+
+        let aim_with_stick = match device {
+            PlayerDevice::Any => *r_last_device == LastInputDevice::Gamepad,
+            PlayerDevice::KeyboardMouse => false,
+            PlayerDevice::Gamepad(_) => true,
+        };
+        let aim = if aim_with_stick {
+            (sticks.right != Vec2::ZERO).then_some(Aim::Direction(sticks.right))
+        } else {
+            devices
+                .viewport
+                .cursor
+                .filter(|_| pressed(InputAction::Aim))
+                .map(Aim::Point)
+        };
+
+        r_inputs.0.push(PlayerInput {
+            movement,
+            aim,
+            fire: pressed(InputAction::Fire),
+            dash: pressed(InputAction::Dash),
+            next_weapon: pressed(InputAction::NextWeapon),
+            previous_weapon: pressed(InputAction::PreviousWeapon),
+            weapon_slot: InputAction::WEAPON_SLOTS
+                .iter()
+                .position(|action| pressed(*action)),
+        });
+    }
+}
@@ -0,0 +1,343 @@
+use bevy::{
+    ecs::system::SystemParam,
+    input::{gamepad::GamepadButton, mouse::MouseWheel, InputSystem},
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::*,
+    settings::{SettingsAppExt, SettingsFile},
+    viewport::sync_viewport_with_window,
+};
+
+use super::PlayerDevice;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<ActionMap>();
+    app.init_settings::<ActionMap>();
+    app.init_resource::<ButtonInput<InputAction>>();
+
+    app.init_resource::<PendingRebind>();
+    app.init_resource::<MouseWheelLatch>();
+    app.add_event::<RebindAction>();
+
+    app.add_systems(
+        PreUpdate,
+        (
+            latch_mouse_wheel,
+            start_rebind,
+            capture_rebind,
+            update_action_state,
+        )
+            .chain()
+            .after(InputSystem)
+            .after(sync_viewport_with_window),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Fire,
+    Aim,
+    Dash,
+    NextWeapon,
+    PreviousWeapon,
+    Weapon1,
+    Weapon2,
+    Weapon3,
+    Weapon4,
+    Pause,
+}
+impl InputAction {
+    pub const ALL: [InputAction; 14] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Fire,
+        InputAction::Aim,
+        InputAction::Dash,
+        InputAction::NextWeapon,
+        InputAction::PreviousWeapon,
+        InputAction::Weapon1,
+        InputAction::Weapon2,
+        InputAction::Weapon3,
+        InputAction::Weapon4,
+        InputAction::Pause,
+    ];
+
+    /// Actions selecting a weapon slot directly, in slot order.
+    pub const WEAPON_SLOTS: [InputAction; 4] = [
+        InputAction::Weapon1,
+        InputAction::Weapon2,
+        InputAction::Weapon3,
+        InputAction::Weapon4,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// Button on any connected gamepad.
+    Gamepad(GamepadButtonType),
+    /// Active while the cursor is over the game.
+    Cursor,
+    /// Active from a scroll until gameplay has read it.
+    WheelUp,
+    WheelDown,
+}
+impl InputBinding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, InputBinding::Gamepad(_))
+    }
+}
+
+/// Devices bound to each action. Ships read them per player device through [`ActionMap::pressed`],
+/// while app-wide actions like pausing go through `ButtonInput<InputAction>`.
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone)]
+#[reflect(Resource)]
+pub struct ActionMap {
+    pub bindings: HashMap<InputAction, Vec<InputBinding>>,
+}
+impl Default for ActionMap {
+    fn default() -> Self {
+        use InputBinding::*;
+        Self {
+            bindings: HashMap::from_iter([
+                (
+                    InputAction::MoveUp,
+                    vec![Key(KeyCode::KeyW), Key(KeyCode::ArrowUp)],
+                ),
+                (
+                    InputAction::MoveDown,
+                    vec![Key(KeyCode::KeyS), Key(KeyCode::ArrowDown)],
+                ),
+                (
+                    InputAction::MoveLeft,
+                    vec![Key(KeyCode::KeyA), Key(KeyCode::ArrowLeft)],
+                ),
+                (
+                    InputAction::MoveRight,
+                    vec![Key(KeyCode::KeyD), Key(KeyCode::ArrowRight)],
+                ),
+                (
+                    InputAction::Fire,
+                    vec![
+                        Mouse(MouseButton::Left),
+                        Gamepad(GamepadButtonType::RightTrigger2),
+                    ],
+                ),
+                (InputAction::Aim, vec![Cursor]),
+                (
+                    InputAction::Dash,
+                    vec![
+                        Key(KeyCode::Space),
+                        Gamepad(GamepadButtonType::LeftTrigger2),
+                    ],
+                ),
+                (
+                    InputAction::NextWeapon,
+                    vec![
+                        Key(KeyCode::KeyE),
+                        WheelUp,
+                        Gamepad(GamepadButtonType::RightTrigger),
+                    ],
+                ),
+                (
+                    InputAction::PreviousWeapon,
+                    vec![
+                        Key(KeyCode::KeyQ),
+                        WheelDown,
+                        Gamepad(GamepadButtonType::LeftTrigger),
+                    ],
+                ),
+                (InputAction::Weapon1, vec![Key(KeyCode::Digit1)]),
+                (InputAction::Weapon2, vec![Key(KeyCode::Digit2)]),
+                (InputAction::Weapon3, vec![Key(KeyCode::Digit3)]),
+                (InputAction::Weapon4, vec![Key(KeyCode::Digit4)]),
+                (
+                    InputAction::Pause,
+                    vec![Key(KeyCode::Escape), Gamepad(GamepadButtonType::Start)],
+                ),
+            ]),
+        }
+    }
+}
+impl SettingsFile for ActionMap {
+    const FILE_NAME: &'static str = "bindings.ron";
+}
+impl ActionMap {
+    pub fn bindings(&self, action: InputAction) -> &[InputBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the bindings of the action from the same kind of device, so rebinding a key
+    /// keeps the gamepad working and the other way around.
+    pub fn rebind(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|bound| bound.is_gamepad() != binding.is_gamepad());
+        bindings.push(binding);
+    }
+
+    /// Whether any binding of the action is held on the given device.
+    pub fn pressed(
+        &self,
+        action: InputAction,
+        devices: &InputDevices,
+        device: &PlayerDevice,
+    ) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| devices.pressed_on(binding, device))
+    }
+
+    pub fn add_binding(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+}
+
+/// Binds the next key, mouse or gamepad button pressed to the action. Escape cancels.
+#[derive(Event)]
+pub struct RebindAction(pub InputAction);
+
+#[derive(Resource, Default)]
+pub struct PendingRebind(pub Option<InputAction>);
+
+fn start_rebind(mut e_rebind: EventReader<RebindAction>, mut r_pending: ResMut<PendingRebind>) {
+    if let Some(RebindAction(action)) = e_rebind.read().last() {
+        r_pending.0 = Some(*action);
+    }
+}
+
+/// Scrolls since the last fixed step, the wheel has no held state to sample.
+#[derive(Resource, Default)]
+pub struct MouseWheelLatch {
+    pub up: bool,
+    pub down: bool,
+}
+
+fn latch_mouse_wheel(mut e_wheel: EventReader<MouseWheel>, mut r_latch: ResMut<MouseWheelLatch>) {
+    for wheel in e_wheel.read() {
+        if wheel.y > 0.0 {
+            r_latch.up = true;
+        } else if wheel.y < 0.0 {
+            r_latch.down = true;
+        }
+    }
+}
+
+/// Lets go of the latched scrolls once they've made it into the player input.
+pub fn clear_mouse_wheel(mut r_latch: ResMut<MouseWheelLatch>) {
+    *r_latch = default();
+}
+
+fn capture_rebind(
+    mut r_pending: ResMut<PendingRebind>,
+    mut r_map: ResMut<ActionMap>,
+    devices: InputDevices,
+) {
+    let Some(action) = r_pending.0 else {
+        return;
+    };
+
+    if devices.kbd.just_pressed(KeyCode::Escape) {
+        r_pending.0 = None;
+        return;
+    }
+
+    if let Some(binding) = devices.just_pressed() {
+        info!("Bound {:?} to {:?}", action, binding);
+        r_map.rebind(action, binding);
+        r_pending.0 = None;
+    }
+}
+
+fn update_action_state(
+    mut r_actions: ResMut<ButtonInput<InputAction>>,
+    r_map: Res<ActionMap>,
+    r_pending: Res<PendingRebind>,
+    devices: InputDevices,
+) {
+    // Whatever is pressed while rebinding is meant for the new binding, not the game. That
+    // includes the press that just finished or cancelled it.
+    let rebinding = r_pending.0.is_some() || r_pending.is_changed();
+    r_actions.clear();
+    for (action, bindings) in r_map.bindings.iter() {
+        let pressed = !rebinding && bindings.iter().any(|b| devices.pressed(b));
+
+        if pressed {
+            r_actions.press(*action);
+        } else {
+            r_actions.release(*action);
+        }
+    }
+}
+
+/// Every device an [`InputBinding`] can refer to.
+#[derive(SystemParam)]
+pub struct InputDevices<'w> {
+    pub kbd: Res<'w, ButtonInput<KeyCode>>,
+    pub mouse: Res<'w, ButtonInput<MouseButton>>,
+    pub gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    pub gamepads: Res<'w, Gamepads>,
+    pub viewport: Res<'w, VirtualViewport>,
+    pub wheel: Res<'w, MouseWheelLatch>,
+}
+impl InputDevices<'_> {
+    pub fn pressed(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(key) => self.kbd.pressed(*key),
+            InputBinding::Mouse(button) => self.mouse.pressed(*button),
+            InputBinding::Gamepad(button) => self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .pressed(GamepadButton::new(gamepad, *button))
+            }),
+            InputBinding::Cursor => self.viewport.cursor.is_some(),
+            InputBinding::WheelUp => self.wheel.up,
+            InputBinding::WheelDown => self.wheel.down,
+        }
+    }
+
+    pub fn pressed_on(&self, binding: &InputBinding, device: &PlayerDevice) -> bool {
+        match binding {
+            InputBinding::Gamepad(button) => self.gamepads.iter().any(|gamepad| {
+                device.uses_gamepad(gamepad)
+                    && self
+                        .gamepad_buttons
+                        .pressed(GamepadButton::new(gamepad, *button))
+            }),
+            _ => device.uses_keyboard_mouse() && self.pressed(binding),
+        }
+    }
+
+    /// Any button pressed this frame, as a binding.
+    pub fn just_pressed(&self) -> Option<InputBinding> {
+        let key = self
+            .kbd
+            .get_just_pressed()
+            .next()
+            .map(|key| InputBinding::Key(*key));
+        let mouse = || {
+            self.mouse
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Mouse(*button))
+        };
+        let gamepad = || {
+            self.gamepad_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Gamepad(button.button_type))
+        };
+        key.or_else(mouse).or_else(gamepad)
+    }
+}
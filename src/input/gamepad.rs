@@ -0,0 +1,156 @@
+use bevy::{
+    input::{
+        gamepad::{GamepadAxisType, GamepadButton},
+        mouse::MouseMotion,
+        InputSystem,
+    },
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::*,
+    settings::{SettingsAppExt, SettingsFile},
+};
+
+use super::PlayerDevice;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<StickSettings>()
+        .register_type::<GamepadSticks>()
+        .register_type::<LastInputDevice>();
+    app.init_settings::<StickSettings>();
+    app.init_resource::<GamepadSticks>();
+    app.init_resource::<LastInputDevice>();
+
+    app.add_systems(
+        PreUpdate,
+        (read_gamepad_sticks, detect_last_input_device)
+            .chain()
+            .after(InputSystem),
+    );
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone)]
+#[reflect(Resource)]
+pub struct StickSettings {
+    /// Stick deflection below this is ignored.
+    pub dead_zone: f32,
+    /// Multiplier on the movement stick, above 1 full speed is reached before full deflection.
+    pub sensitivity: f32,
+}
+impl Default for StickSettings {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.2,
+            sensitivity: 1.0,
+        }
+    }
+}
+impl SettingsFile for StickSettings {
+    const FILE_NAME: &'static str = "gamepad.ron";
+}
+
+/// Stick positions of every connected gamepad, with the dead zone already applied.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct GamepadSticks(pub HashMap<Gamepad, Sticks>);
+impl GamepadSticks {
+    /// Sticks of every gamepad the device uses, combined.
+    pub fn for_device(&self, device: &PlayerDevice) -> Sticks {
+        let mut sticks = Sticks::default();
+        for (_, pad_sticks) in self.0.iter().filter(|(pad, _)| device.uses_gamepad(**pad)) {
+            sticks.left += pad_sticks.left;
+            sticks.right += pad_sticks.right;
+        }
+        sticks.left = sticks.left.clamp_length_max(1.0);
+        sticks.right = sticks.right.clamp_length_max(1.0);
+        sticks
+    }
+}
+
+#[derive(Reflect, Default, Debug, Clone, Copy)]
+pub struct Sticks {
+    pub left: Vec2,
+    pub right: Vec2,
+}
+
+/// Device the player used last, decides whether the ship aims at the cursor or with the stick.
+#[derive(Resource, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum LastInputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad,
+}
+
+fn read_gamepad_sticks(
+    mut r_sticks: ResMut<GamepadSticks>,
+    r_settings: Res<StickSettings>,
+    r_gamepads: Res<Gamepads>,
+    r_axes: Res<Axis<GamepadAxis>>,
+) {
+    let stick = |gamepad, x, y| {
+        Vec2::new(
+            r_axes.get(GamepadAxis::new(gamepad, x)).unwrap_or(0.0),
+            r_axes.get(GamepadAxis::new(gamepad, y)).unwrap_or(0.0),
+        )
+    };
+
+    r_sticks.0.clear();
+    for gamepad in r_gamepads.iter() {
+        let left = stick(
+            gamepad,
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+        );
+        let right = stick(
+            gamepad,
+            GamepadAxisType::RightStickX,
+            GamepadAxisType::RightStickY,
+        );
+
+        let sticks = Sticks {
+            left: (apply_dead_zone(left, r_settings.dead_zone) * r_settings.sensitivity)
+                .clamp_length_max(1.0),
+            right: apply_dead_zone(right, r_settings.dead_zone),
+        };
+        r_sticks.0.insert(gamepad, sticks);
+    }
+}
+
+/// Rescales the stick so it starts from zero right outside the dead zone.
+pub fn apply_dead_zone(stick: Vec2, dead_zone: f32) -> Vec2 {
+    let length = stick.length();
+    if length <= dead_zone {
+        return Vec2::ZERO;
+    }
+    stick / length * ((length - dead_zone) / (1.0 - dead_zone)).min(1.0)
+}
+
+fn detect_last_input_device(
+    mut r_device: ResMut<LastInputDevice>,
+    r_sticks: Res<GamepadSticks>,
+    kbd: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut e_mouse_motion: EventReader<MouseMotion>,
+) {
+    let mouse_moved = e_mouse_motion.read().count() > 0;
+    let sticks_moved = r_sticks
+        .0
+        .values()
+        .any(|sticks| sticks.left != Vec2::ZERO || sticks.right != Vec2::ZERO);
+    let device = if gamepad_buttons.get_just_pressed().len() > 0 || sticks_moved {
+        LastInputDevice::Gamepad
+    } else if kbd.get_just_pressed().len() > 0 || mouse.get_just_pressed().len() > 0 || mouse_moved
+    {
+        LastInputDevice::KeyboardMouse
+    } else {
+        return;
+    };
+
+    if *r_device != device {
+        *r_device = device;
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::input::gamepad::GamepadButton;
+
+use crate::prelude::*;
+
+pub const MAX_LOCAL_PLAYERS: usize = 4;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<LocalPlayers>();
+
+    app.add_systems(
+        Update,
+        join_local_players.run_if(in_state(GameStates::Menu)),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PlayerDevice {
+    /// Keyboard, mouse and every gamepad, used while playing alone.
+    Any,
+    KeyboardMouse,
+    Gamepad(Gamepad),
+}
+impl PlayerDevice {
+    pub fn uses_keyboard_mouse(&self) -> bool {
+        matches!(self, PlayerDevice::Any | PlayerDevice::KeyboardMouse)
+    }
+
+    pub fn uses_gamepad(&self, gamepad: Gamepad) -> bool {
+        match self {
+            PlayerDevice::Any => true,
+            PlayerDevice::KeyboardMouse => false,
+            PlayerDevice::Gamepad(device) => *device == gamepad,
+        }
+    }
+}
+
+/// Device of each local player, the position in the list is the player index.
+#[derive(Resource, Debug)]
+pub struct LocalPlayers(pub Vec<PlayerDevice>);
+impl Default for LocalPlayers {
+    fn default() -> Self {
+        Self(vec![PlayerDevice::Any])
+    }
+}
+
+/// Pressing start on a gamepad that isn't playing yet adds a new player.
+fn join_local_players(
+    mut r_players: ResMut<LocalPlayers>,
+    r_gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+) {
+    for gamepad in r_gamepads.iter() {
+        let joined = r_players.0.contains(&PlayerDevice::Gamepad(gamepad));
+        if joined
+            || r_players.0.len() >= MAX_LOCAL_PLAYERS
+            || !gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start))
+        {
+            continue;
+        }
+
+        // Whoever was playing alone keeps the keyboard and leaves the gamepads to the rest.
+        for device in r_players.0.iter_mut() {
+            if *device == PlayerDevice::Any {
+                *device = PlayerDevice::KeyboardMouse;
+            }
+        }
+        r_players.0.push(PlayerDevice::Gamepad(gamepad));
+        info!("Player {} joined with {:?}", r_players.0.len(), gamepad);
+    }
+}
@@ -0,0 +1,133 @@
+pub mod asteroids;
+pub mod camera;
+pub mod common;
+pub mod culling;
+pub mod headless;
+pub mod input;
+pub mod player;
+pub mod prelude;
+pub mod projectiles;
+pub mod replay;
+pub mod scenes;
+pub mod score;
+pub mod settings;
+pub mod shooter;
+pub mod spawner;
+pub mod ufo;
+pub mod ui;
+pub mod viewport;
+pub mod weapons;
+
+use bevy::{app::PluginGroupBuilder, ecs::schedule::ExecutorKind, sprite::Wireframe2dPlugin};
+use bevy_inspector_egui::quick::WorldInspectorPlugin;
+use prelude::*;
+
+/// Rate of the fixed timestep gameplay and physics run at.
+pub const FIXED_TIMESTEP_HZ: f64 = 60.0;
+
+/// Everything needed to run the game, engine plugins included.
+/// Parts that need a GPU can be turned off to embed the game in tests and tools.
+pub struct AsteroidsPlugins {
+    /// Engine plugins matching `rendering`. Disable to add the game to an app that
+    /// already set up its own window, renderer or headless runner.
+    pub engine: bool,
+    /// Window and renderer. When disabled the app runs headless.
+    pub rendering: bool,
+    /// Particle effects, requires rendering.
+    pub vfx: bool,
+    /// World inspector window, requires rendering.
+    pub inspector: bool,
+}
+impl Default for AsteroidsPlugins {
+    fn default() -> Self {
+        Self {
+            engine: true,
+            rendering: true,
+            vfx: true,
+            inspector: true,
+        }
+    }
+}
+impl AsteroidsPlugins {
+    pub fn headless() -> Self {
+        Self {
+            engine: true,
+            rendering: false,
+            vfx: false,
+            inspector: false,
+        }
+    }
+
+    /// Only the game, for apps that bring their own engine plugins.
+    pub fn without_engine(self) -> Self {
+        Self {
+            engine: false,
+            ..self
+        }
+    }
+}
+impl PluginGroup for AsteroidsPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let mut group = PluginGroupBuilder::start::<Self>();
+
+        // The graphics backend is picked per platform, `WGPU_BACKEND` overrides it.
+        if self.engine && self.rendering {
+            group = group.add_group(DefaultPlugins).add(Wireframe2dPlugin);
+        } else if self.engine {
+            group = group.add(headless::plugin);
+        }
+
+        if self.rendering && self.vfx {
+            group = group.add(HanabiPlugin);
+        } else {
+            group = group.add(disabled_vfx);
+        }
+
+        if self.rendering && self.inspector {
+            group = group.add(WorldInspectorPlugin::new());
+        }
+
+        group
+            .add(fixed_timestep)
+            .add(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0).in_fixed_schedule())
+            // .add(RapierDebugRenderPlugin::default())
+            .add(bevy_tweening::TweeningPlugin)
+            .add(common::plugin)
+            .add(input::plugin)
+            .add(player::plugin)
+            .add(camera::plugin)
+            .add(shooter::plugin)
+            .add(weapons::plugin)
+            .add(asteroids::plugin)
+            .add(ufo::plugin)
+            .add(projectiles::plugin)
+            .add(culling::plugin)
+            .add(scenes::plugin)
+            .add(score::plugin)
+            .add(spawner::plugin)
+            .add(ui::plugin)
+            .add(viewport::plugin)
+            .add(replay::plugin)
+    }
+}
+
+/// Gameplay must play out the same every run to be replayed, so it runs on a fixed
+/// timestep in a fixed system order.
+fn fixed_timestep(app: &mut App) {
+    app.insert_resource(Time::<Fixed>::from_hz(FIXED_TIMESTEP_HZ));
+    app.insert_resource(RapierConfiguration {
+        timestep_mode: TimestepMode::Fixed {
+            dt: (1.0 / FIXED_TIMESTEP_HZ) as f32,
+            substeps: 1,
+        },
+        ..RapierConfiguration::new(100.0)
+    });
+    app.edit_schedule(FixedUpdate, |schedule| {
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    });
+}
+
+/// Effects are still spawned by gameplay, they just never get simulated.
+fn disabled_vfx(app: &mut App) {
+    app.init_asset::<EffectAsset>();
+}
@@ -1,8 +1,19 @@
 use std::time::Duration;
 
+pub mod dash;
+pub mod flight;
+pub mod respawn;
+pub mod shield;
+pub use dash::*;
+pub use flight::*;
+pub use respawn::*;
+pub use shield::*;
+
 use crate::common::*;
+use crate::input::PlayerInputs;
 use crate::scenes::GameStates;
 use crate::shooter::*;
+use crate::weapons::WeaponInventory;
 use bevy::{
     ecs::{system::RunSystemOnce, world::Command},
     prelude::*,
@@ -11,26 +22,62 @@ use bevy::{
 use bevy_rapier2d::prelude::*;
 
 pub fn plugin(app: &mut App) {
+    app.add_plugins((
+        dash::plugin,
+        flight::plugin,
+        respawn::plugin,
+        shield::plugin,
+    ));
+
     app.register_type::<Player>();
+    app.init_resource::<SpawnedPlayers>();
 
     app.add_systems(
-        Update,
+        FixedUpdate,
         (
             player_movement,
             player_look_at_mouse,
             player_input_shooting,
             player_death_touch,
-        ),
+            spawn_joined_players,
+        )
+            .in_set(GameplaySet),
     );
+    // Everyone gets a new ship when going back to the menu.
+    app.add_systems(OnEnter(GameStates::Menu), reset_spawned_players);
 
-    app.observe(on_player_death);
+    app.observe(on_player_hit)
+        .observe(on_player_killed)
+        .observe(on_player_death);
 }
 
+/// Health of a fresh ship. Touching anything with [`KillPlayerOnTouch`] is deadly regardless.
+pub const PLAYER_HEALTH: f32 = 5.0;
+
+pub const PLAYER_COLORS: [Srgba; 4] = [
+    Srgba::WHITE,
+    Srgba::rgb(0.3, 0.7, 1.0),
+    Srgba::rgb(1.0, 0.4, 0.4),
+    Srgba::rgb(0.5, 1.0, 0.5),
+];
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-pub struct Player;
+pub struct Player {
+    /// Index into [`LocalPlayers`](crate::input::LocalPlayers) and [`PlayerInputs`].
+    pub index: usize,
+}
+
+/// How many players have had a ship spawned since the last time in the menu.
+#[derive(Resource, Default)]
+pub struct SpawnedPlayers(pub usize);
 
-pub struct SpawnPlayer;
+pub struct SpawnPlayer {
+    pub index: usize,
+    pub position: Vec2,
+    /// Seconds the new ship starts [`Invulnerable`] for, none if zero.
+    pub invulnerability: f32,
+}
 impl Command for SpawnPlayer {
     fn apply(self, world: &mut World) {
         world.run_system_once_with(self, spawn_player);
@@ -41,14 +88,25 @@ fn spawn_player(
     mut cmds: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    r_flight: Res<FlightSettings>,
+    r_assets: Res<AssetServer>,
 ) {
-    cmds.spawn((
-        Name::new("Player"),
-        Player,
-        Shooter {
-            shoot_delay: 0.4,
-            ..default()
-        },
+    let index = spawn.index;
+    let weapon = r_assets.load("weapons/blaster.weapon.ron");
+    let mut player = cmds.spawn((
+        Name::new(format!("Player {}", index + 1)),
+        Player { index },
+        FactionBundle::body(Faction::Player),
+        // Lasers hit every step, the short window keeps them from melting ships instantly.
+        Health::new(PLAYER_HEALTH).with_invulnerability(0.5),
+        GuardedHealth,
+        ShipFlight::new(r_flight.flight_model),
+        Dash::default(),
+        Shield::default(),
+        // Mostly felt from weapon recoil and the player's own explosions.
+        Knockback::new(100.0),
+        Shooter::new(weapon.clone()),
+        WeaponInventory::new(weapon),
         PickUpReceiver {
             check_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
             chase_distance: 500.0,
@@ -62,7 +120,8 @@ fn spawn_player(
                 Vec2::new(-50.0, -50.0),
                 Vec2::new(50.0, -50.0),
             ))),
-            material: materials.add(Color::linear_rgb(1.0, 1.0, 1.0)),
+            material: materials.add(Color::from(PLAYER_COLORS[index % PLAYER_COLORS.len()])),
+            transform: Transform::from_translation(spawn.position.extend(0.0)),
             ..default()
         },
         PhysicsBundle {
@@ -81,6 +140,10 @@ fn spawn_player(
             ..default()
         },
     ));
+
+    if spawn.invulnerability > 0.0 {
+        player.insert(Invulnerable::new(spawn.invulnerability));
+    }
 }
 
 #[derive(Component, Default)]
@@ -90,86 +153,187 @@ pub struct KillPlayerOnTouch;
 pub struct OnPlayerDeath;
 
 fn player_movement(
-    mut q_players: Query<&mut Velocity, With<Player>>,
-    kbd: Res<ButtonInput<KeyCode>>,
+    mut q_players: Query<(
+        &Player,
+        &ShipFlight,
+        Option<&Dash>,
+        &Transform,
+        &mut Velocity,
+    )>,
+    r_inputs: Res<PlayerInputs>,
     time: Res<Time>,
 ) {
-    if q_players.is_empty() {
-        return;
-    }
+    for (player, flight, dash, xform, mut velocity) in q_players.iter_mut() {
+        let input = r_inputs.get(player.index);
+        *velocity = flight.fly(
+            xform.up().xy(),
+            input.movement,
+            *velocity,
+            time.delta_seconds(),
+        );
 
-    let mut player = q_players.single_mut();
-    let mut force = Vec2::ZERO;
-    if kbd.pressed(KeyCode::KeyW) {
-        force += Vec2::Y;
-    }
-    if kbd.pressed(KeyCode::KeyS) {
-        force -= Vec2::Y;
-    }
-    if kbd.pressed(KeyCode::KeyD) {
-        force += Vec2::X;
-    }
-    if kbd.pressed(KeyCode::KeyA) {
-        force -= Vec2::X;
+        // Dashing goes over the max speed for a moment.
+        if !dash.is_some_and(Dash::is_dashing) {
+            velocity.linvel = velocity.linvel.clamp_length_max(flight.max_speed);
+        }
     }
-    player.linvel += force * 2000.0 * time.delta_seconds();
-    player.linvel = player.linvel.clamp_length(0.0, 1250.0);
 }
 
 fn player_look_at_mouse(
-    mut q_players: Query<&mut Transform, With<Player>>,
-    q_wnd: Query<&Window>,
-    q_cam: Query<(&Camera, &GlobalTransform)>,
+    mut q_players: Query<(&Player, &ShipFlight, &mut Transform)>,
+    r_inputs: Res<PlayerInputs>,
 ) {
-    if q_players.is_empty() {
-        return;
-    }
-    let (camera, camera_xform) = q_cam.single();
-    let window = q_wnd.single();
-    let mut player = q_players.single_mut();
-
-    if let Some(pos) = window
-        .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_xform, cursor))
-    {
-        let player_pos = player.translation;
-        let player_forward = player.forward().as_vec3();
-        player.look_at(player_pos + player_forward, pos.extend(0.0) - player_pos);
+    for (player, flight, mut xform) in q_players.iter_mut() {
+        // Classic ships only point where they're steered.
+        if flight.model == FlightModel::Classic {
+            continue;
+        }
+
+        if let Some(aim) = r_inputs.get(player.index).aim {
+            let player_pos = xform.translation;
+            let player_forward = xform.forward().as_vec3();
+            let aim_dir = aim.direction_from(player_pos.xy());
+            xform.look_at(player_pos + player_forward, aim_dir.extend(0.0));
+        }
     }
 }
 
 fn player_input_shooting(
-    mut q_players: Query<&mut Shooter, With<Player>>,
-    kbd: Res<ButtonInput<MouseButton>>,
+    mut q_players: Query<(&Player, &mut Shooter)>,
+    r_inputs: Res<PlayerInputs>,
 ) {
-    if q_players.is_empty() {
-        return;
+    for (player, mut shooter) in q_players.iter_mut() {
+        shooter.enabled = r_inputs.get(player.index).fire;
     }
-
-    let mut player = q_players.single_mut();
-    player.enabled = kbd.pressed(MouseButton::Left);
 }
 
 fn player_death_touch(
     mut cmds: Commands,
-    mut q_players: Query<Entity, With<Player>>,
-    q_deathtouch: Query<Entity, With<KillPlayerOnTouch>>,
+    mut q_players: Query<(Entity, &GlobalTransform, &Health, Option<&mut Shield>), With<Player>>,
+    q_invulnerable: Query<(), With<Invulnerable>>,
+    q_dashes: Query<&Dash>,
+    q_deathtouch: Query<&GlobalTransform, With<KillPlayerOnTouch>>,
+    mut q_knockbacks: Query<&mut Knockback>,
     r_rapier: Res<RapierContext>,
 ) {
-    let player = q_players.single();
-    for pair in r_rapier.contact_pairs_with(player) {
-        let other = if pair.collider1() == player {
-            pair.collider2()
-        } else {
-            pair.collider1()
-        };
-        if q_deathtouch.get(other).is_ok() {
-            cmds.trigger_targets(OnPlayerDeath, player);
-            println!("Holy fuck the player is dead!");
+    for (player, xform, health, mut shield) in q_players.iter_mut() {
+        let dashing = q_dashes.get(player).is_ok_and(Dash::is_invulnerable);
+        if q_invulnerable.contains(player) || dashing {
+            continue;
         }
+
+        // Pairs only overlapping in their bounding boxes aren't touching yet.
+        for pair in r_rapier
+            .contact_pairs_with(player)
+            .filter(|pair| pair.has_any_active_contact())
+        {
+            let other = if pair.collider1() == player {
+                pair.collider2()
+            } else {
+                pair.collider1()
+            };
+            let Ok(other_xform) = q_deathtouch.get(other) else {
+                continue;
+            };
+
+            if let Some(shield) = shield.as_mut().filter(|shield| shield.is_up()) {
+                shield.absorb();
+                let away = (other_xform.translation() - xform.translation())
+                    .xy()
+                    .normalize_or_zero();
+                let impulse = away * shield.bounce_impulse;
+                match q_knockbacks.get_mut(other) {
+                    Ok(mut knockback) => knockback.push(impulse),
+                    // The other entity may have been killed on the same step.
+                    Err(_) => {
+                        cmds.entity(other).try_insert(ExternalImpulse {
+                            impulse,
+                            ..default()
+                        });
+                    }
+                }
+                continue;
+            }
+
+            cmds.add(ApplyDamage {
+                target: player,
+                hit: HitData {
+                    point: xform.translation(),
+                    dir: Dir3::new(xform.translation() - other_xform.translation())
+                        .unwrap_or(Dir3::Y),
+                    dealer: other,
+                    damage: health.current,
+                    // Surviving a bullet a moment ago doesn't make touching rocks safe.
+                    ignore_invulnerability: true,
+                    ..default()
+                },
+            });
+            break;
+        }
+    }
+}
+
+/// Spawns a ship for every player that has input but no ship yet. Going by the input keeps
+/// players joining in the middle of a replay in sync with the recording.
+pub(crate) fn spawn_joined_players(
+    mut cmds: Commands,
+    mut r_spawned: ResMut<SpawnedPlayers>,
+    r_inputs: Res<PlayerInputs>,
+) {
+    while r_spawned.0 < r_inputs.0.len() {
+        cmds.add(SpawnPlayer {
+            index: r_spawned.0,
+            position: Vec2::new(r_spawned.0 as f32 * 150.0, 0.0),
+            invulnerability: 0.0,
+        });
+        r_spawned.0 += 1;
+    }
+}
+
+fn reset_spawned_players(mut r_spawned: ResMut<SpawnedPlayers>) {
+    r_spawned.0 = 0;
+}
+
+/// Centre of every living player, used to frame them together.
+pub fn players_center<'a>(players: impl Iterator<Item = &'a GlobalTransform>) -> Option<Vec2> {
+    let (sum, count) = players.fold((Vec2::ZERO, 0), |(sum, count), xform| {
+        (sum + xform.translation().xy(), count + 1)
+    });
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Hits only take health off ships that aren't protected, the shield absorbing them first.
+fn on_player_hit(
+    e_hit: Trigger<OnHit>,
+    mut cmds: Commands,
+    mut q_shields: Query<Option<&mut Shield>, With<Player>>,
+    q_invulnerable: Query<(), With<Invulnerable>>,
+    q_dashes: Query<&Dash>,
+) {
+    let player = e_hit.entity();
+    let Ok(shield) = q_shields.get_mut(player) else {
+        return;
+    };
+    let dashing = q_dashes.get(player).is_ok_and(Dash::is_invulnerable);
+    if q_invulnerable.contains(player) || dashing {
+        return;
+    }
+
+    if shield.is_some_and(|mut shield| shield.absorb()) {
+        return;
+    }
+    cmds.add(ApplyDamage {
+        target: player,
+        hit: e_hit.event().0,
+    });
+}
+
+fn on_player_killed(e_death: Trigger<OnDeath>, mut cmds: Commands, q_players: Query<&Player>) {
+    if q_players.contains(e_death.entity()) {
+        cmds.trigger_targets(OnPlayerDeath, e_death.entity());
     }
 }
 
 fn on_player_death(e_player_death: Trigger<OnPlayerDeath>, mut cmds: Commands) {
-    cmds.entity(e_player_death.entity()).despawn();
+    cmds.entity(e_player_death.entity()).despawn_recursive();
 }
@@ -0,0 +1,122 @@
+use crate::{input::PlayerInputs, prelude::*};
+
+use super::{player_movement, FlightModel, ShipFlight};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Dash>();
+
+    app.add_systems(
+        FixedUpdate,
+        (tick_dashes, player_dash)
+            .chain()
+            .before(player_movement)
+            .in_set(GameplaySet),
+    );
+}
+
+/// Burst of speed on the dash action. Charges refill one at a time after the cooldown.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Dash {
+    /// Speed added in the dash direction.
+    pub impulse: f32,
+    /// Seconds the ship can go over its max speed.
+    pub duration: f32,
+    /// Seconds the ship ignores [`KillPlayerOnTouch`] after dashing.
+    pub invulnerability: f32,
+    /// Seconds to refill a charge.
+    pub cooldown: f32,
+    pub max_charges: u32,
+    pub charges: u32,
+    pub cooldown_timer: f32,
+    /// Seconds since the last dash started.
+    pub elapsed: f32,
+    /// Dash button state on the previous step, dashes only start on a new press.
+    pub was_pressed: bool,
+}
+impl Default for Dash {
+    fn default() -> Self {
+        Self {
+            impulse: 2500.0,
+            duration: 0.25,
+            invulnerability: 0.3,
+            cooldown: 1.5,
+            max_charges: 2,
+            charges: 2,
+            cooldown_timer: 0.0,
+            elapsed: f32::INFINITY,
+            was_pressed: false,
+        }
+    }
+}
+impl Dash {
+    pub fn is_dashing(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.elapsed < self.invulnerability
+    }
+}
+
+#[derive(Event)]
+pub struct OnDashStarted {
+    pub direction: Vec2,
+}
+
+#[derive(Event)]
+pub struct OnDashEnded;
+
+fn tick_dashes(mut cmds: Commands, mut q_dashes: Query<(Entity, &mut Dash)>, r_time: Res<Time>) {
+    for (e, mut dash) in q_dashes.iter_mut() {
+        let was_dashing = dash.is_dashing();
+        dash.elapsed += r_time.delta_seconds();
+        if was_dashing && !dash.is_dashing() {
+            cmds.trigger_targets(OnDashEnded, e);
+        }
+
+        if dash.charges < dash.max_charges {
+            dash.cooldown_timer += r_time.delta_seconds();
+            if dash.cooldown_timer >= dash.cooldown {
+                dash.cooldown_timer -= dash.cooldown;
+                dash.charges += 1;
+            }
+        } else {
+            dash.cooldown_timer = 0.0;
+        }
+    }
+}
+
+fn player_dash(
+    mut cmds: Commands,
+    mut q_players: Query<(
+        Entity,
+        &Player,
+        &ShipFlight,
+        &Transform,
+        &mut Dash,
+        &mut Velocity,
+    )>,
+    r_inputs: Res<PlayerInputs>,
+) {
+    for (e, player, flight, xform, mut dash, mut velocity) in q_players.iter_mut() {
+        let input = r_inputs.get(player.index);
+        let pressed = input.dash && !dash.was_pressed;
+        dash.was_pressed = input.dash;
+        if !pressed || dash.charges == 0 {
+            continue;
+        }
+
+        // Classic ships steer with the movement input, so they always dash forward.
+        let direction = match flight.model {
+            FlightModel::Strafe => input.movement.try_normalize(),
+            FlightModel::Classic => None,
+        }
+        .unwrap_or(xform.up().xy());
+
+        dash.charges -= 1;
+        dash.elapsed = 0.0;
+        velocity.linvel += direction * dash.impulse;
+        cmds.trigger_targets(OnDashStarted { direction }, e);
+    }
+}
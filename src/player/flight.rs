@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::*,
+    settings::{SettingsAppExt, SettingsFile},
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<FlightSettings>()
+        .register_type::<ShipFlight>();
+    app.init_settings::<FlightSettings>();
+
+    app.add_systems(
+        FixedUpdate,
+        (
+            apply_flight_settings.run_if(resource_changed::<FlightSettings>),
+            sync_flight_damping,
+        )
+            .chain()
+            .in_set(GameplaySet),
+    );
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlightModel {
+    /// Movement input pushes along the world axes and the ship looks where it aims.
+    #[default]
+    Strafe,
+    /// Left and right rotate the ship, up thrusts along its facing and down reverses.
+    Classic,
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Default)]
+#[reflect(Resource)]
+pub struct FlightSettings {
+    /// Flight model of every player ship.
+    pub flight_model: FlightModel,
+}
+impl SettingsFile for FlightSettings {
+    const FILE_NAME: &'static str = "flight.ron";
+}
+
+/// How a ship turns movement input into motion.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct ShipFlight {
+    pub model: FlightModel,
+    /// Acceleration at full input.
+    pub thrust: f32,
+    /// Fraction of the thrust used when reversing in the classic model, zero disables it.
+    pub reverse_thrust: f32,
+    pub max_speed: f32,
+    /// Angular acceleration at full input in the classic model, in radians.
+    pub turn_acceleration: f32,
+    pub max_turn_speed: f32,
+    /// Angular damping in the classic model, so the ship stops turning once released.
+    pub angular_damping: f32,
+}
+impl ShipFlight {
+    pub fn new(model: FlightModel) -> Self {
+        Self {
+            model,
+            thrust: 2000.0,
+            reverse_thrust: 0.5,
+            max_speed: 1250.0,
+            turn_acceleration: 60.0,
+            max_turn_speed: 6.0,
+            angular_damping: 10.0,
+        }
+    }
+
+    /// Velocity after a step with the given input, before clamping to `max_speed`.
+    pub fn fly(&self, up: Vec2, input: Vec2, mut velocity: Velocity, dt: f32) -> Velocity {
+        match self.model {
+            FlightModel::Strafe => {
+                velocity.linvel += input * self.thrust * dt;
+            }
+            FlightModel::Classic => {
+                let throttle = if input.y < 0.0 {
+                    input.y * self.reverse_thrust
+                } else {
+                    input.y
+                };
+                velocity.linvel += up * throttle * self.thrust * dt;
+                velocity.angvel = (velocity.angvel - input.x * self.turn_acceleration * dt)
+                    .clamp(-self.max_turn_speed, self.max_turn_speed);
+            }
+        }
+        velocity
+    }
+}
+
+fn apply_flight_settings(
+    mut q_ships: Query<&mut ShipFlight, With<Player>>,
+    r_settings: Res<FlightSettings>,
+) {
+    for mut flight in q_ships.iter_mut() {
+        if flight.model != r_settings.flight_model {
+            flight.model = r_settings.flight_model;
+        }
+    }
+}
+
+fn sync_flight_damping(mut q_ships: Query<(&ShipFlight, &mut Damping), Changed<ShipFlight>>) {
+    for (flight, mut damping) in q_ships.iter_mut() {
+        damping.angular_damping = match flight.model {
+            FlightModel::Strafe => 0.0,
+            FlightModel::Classic => flight.angular_damping,
+        };
+    }
+}
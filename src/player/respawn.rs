@@ -0,0 +1,178 @@
+use std::f32::consts::TAU;
+
+use crate::prelude::*;
+
+use super::{players_center, SpawnPlayer};
+
+pub const STARTING_LIVES: u32 = 3;
+pub const RESPAWN_DELAY: f32 = 2.0;
+pub const RESPAWN_INVULNERABILITY: f32 = 3.0;
+/// Radius around a respawn point that has to be free of anything deadly.
+pub const SAFE_RESPAWN_RADIUS: f32 = 400.0;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Invulnerable>();
+    app.register_type::<Respawning>();
+    app.register_type::<PlayerLives>();
+    app.init_resource::<PlayerLives>();
+
+    app.add_systems(OnEnter(GameStates::Match), reset_lives);
+    app.add_systems(
+        FixedUpdate,
+        (tick_respawns, blink_invulnerable).in_set(GameplaySet),
+    );
+
+    app.observe(queue_respawn);
+}
+
+/// Lives left of each player by index, players that haven't died yet have all of them.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct PlayerLives(pub Vec<u32>);
+impl PlayerLives {
+    pub fn remaining(&self, index: usize) -> u32 {
+        self.0.get(index).copied().unwrap_or(STARTING_LIVES)
+    }
+
+    /// Takes a life from the player and returns how many are left.
+    pub fn lose_life(&mut self, index: usize) -> u32 {
+        if self.0.len() <= index {
+            self.0.resize(index + 1, STARTING_LIVES);
+        }
+        self.0[index] = self.0[index].saturating_sub(1);
+        self.0[index]
+    }
+}
+
+/// Ship can't be hurt by hits or [`KillPlayerOnTouch`] and blinks until the timer runs out.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+    pub blink_timer: Timer,
+}
+impl Invulnerable {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            blink_timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Player waiting to get a new ship.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Respawning {
+    pub index: usize,
+    pub timer: Timer,
+    /// Where the ship died, used to respawn when no other player is alive.
+    pub position: Vec2,
+}
+
+fn reset_lives(mut r_lives: ResMut<PlayerLives>) {
+    r_lives.0.clear();
+}
+
+fn queue_respawn(
+    e_player_death: Trigger<OnPlayerDeath>,
+    mut cmds: Commands,
+    q_players: Query<(&Player, &GlobalTransform)>,
+    mut r_lives: ResMut<PlayerLives>,
+    r_state: Res<State<GameStates>>,
+) {
+    let Ok((player, xform)) = q_players.get(e_player_death.entity()) else {
+        return;
+    };
+
+    // Dying in the menu doesn't cost anything.
+    if *r_state.get() == GameStates::Match {
+        let lives = r_lives.lose_life(player.index);
+        info!("Player {} has {} lives left", player.index + 1, lives);
+        if lives == 0 {
+            return;
+        }
+    }
+
+    cmds.spawn((
+        Name::new(format!("Respawn {}", player.index + 1)),
+        Respawning {
+            index: player.index,
+            timer: Timer::from_seconds(RESPAWN_DELAY, TimerMode::Once),
+            position: xform.translation().xy(),
+        },
+    ));
+}
+
+fn tick_respawns(
+    mut cmds: Commands,
+    mut q_respawns: Query<(Entity, &mut Respawning)>,
+    q_players: Query<&GlobalTransform, With<Player>>,
+    q_deadly: Query<(), With<KillPlayerOnTouch>>,
+    r_rapier: Res<RapierContext>,
+    r_time: Res<Time>,
+) {
+    for (e, mut respawn) in q_respawns.iter_mut() {
+        respawn.timer.tick(r_time.delta());
+        if !respawn.timer.finished() {
+            continue;
+        }
+
+        let origin = players_center(q_players.iter()).unwrap_or(respawn.position);
+        let is_deadly = |e| q_deadly.contains(e);
+        cmds.add(SpawnPlayer {
+            index: respawn.index,
+            position: find_safe_spot(&r_rapier, origin, &is_deadly),
+            invulnerability: RESPAWN_INVULNERABILITY,
+        });
+        cmds.entity(e).despawn();
+    }
+}
+
+/// Looks for the closest spot to `origin` without anything deadly around, checking rings
+/// of points further and further away. Falls back to `origin` if everything is crowded.
+pub fn find_safe_spot(
+    rapier: &RapierContext,
+    origin: Vec2,
+    is_deadly: &impl Fn(Entity) -> bool,
+) -> Vec2 {
+    let shape = Collider::ball(SAFE_RESPAWN_RADIUS);
+    let filter = QueryFilter::default().predicate(is_deadly);
+
+    for ring in 0..8 {
+        let distance = ring as f32 * SAFE_RESPAWN_RADIUS;
+        let points = (ring * 8).max(1);
+        for point in 0..points {
+            let angle = TAU * point as f32 / points as f32;
+            let position = origin + Vec2::from_angle(angle) * distance;
+            if rapier
+                .intersection_with_shape(position, 0.0, &shape, filter)
+                .is_none()
+            {
+                return position;
+            }
+        }
+    }
+    origin
+}
+
+fn blink_invulnerable(
+    mut cmds: Commands,
+    mut q_invulnerable: Query<(Entity, &mut Invulnerable, &mut Visibility)>,
+    r_time: Res<Time>,
+) {
+    for (e, mut invulnerable, mut visibility) in q_invulnerable.iter_mut() {
+        invulnerable.timer.tick(r_time.delta());
+        invulnerable.blink_timer.tick(r_time.delta());
+
+        if invulnerable.timer.finished() {
+            *visibility = Visibility::Inherited;
+            cmds.entity(e).remove::<Invulnerable>();
+        } else if invulnerable.blink_timer.just_finished() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}
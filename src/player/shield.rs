@@ -0,0 +1,139 @@
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+
+use crate::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Shield>();
+
+    app.add_systems(FixedUpdate, recharge_shields.in_set(GameplaySet));
+    app.add_systems(Update, (add_shield_rings, update_shield_rings).chain());
+}
+
+/// Absorbs contacts with [`KillPlayerOnTouch`] entities, bouncing them away, while it has
+/// charges left. Charges come back one by one after a while without hits.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Shield {
+    pub max_charges: u32,
+    pub charges: u32,
+    /// Seconds without hits before recharging starts.
+    pub recharge_delay: f32,
+    /// Seconds to recharge each charge.
+    pub recharge_time: f32,
+    /// Seconds since the last absorbed contact.
+    pub since_hit: f32,
+    pub recharge_timer: f32,
+    /// Seconds after absorbing a contact during which contacts are absorbed for free, so a
+    /// single touch doesn't drain every charge.
+    pub grace: f32,
+    /// Impulse pushing away whatever hit the shield.
+    pub bounce_impulse: f32,
+    pub radius: f32,
+}
+impl Default for Shield {
+    fn default() -> Self {
+        Self {
+            max_charges: 3,
+            charges: 3,
+            recharge_delay: 3.0,
+            recharge_time: 2.0,
+            since_hit: f32::INFINITY,
+            recharge_timer: 0.0,
+            grace: 0.3,
+            bounce_impulse: 1500.0,
+            radius: 90.0,
+        }
+    }
+}
+impl Shield {
+    pub fn is_up(&self) -> bool {
+        self.charges > 0 || self.since_hit < self.grace
+    }
+
+    /// Takes a charge for a contact, returns false if the shield was already down.
+    pub fn absorb(&mut self) -> bool {
+        if self.since_hit < self.grace {
+            return true;
+        }
+        if self.charges == 0 {
+            return false;
+        }
+
+        self.charges -= 1;
+        self.since_hit = 0.0;
+        self.recharge_timer = 0.0;
+        true
+    }
+}
+
+/// Ring showing the shield of the parent, fades out as charges are used.
+#[derive(Component)]
+pub struct ShieldRing {
+    pub material: Handle<ColorMaterial>,
+}
+
+fn recharge_shields(mut q_shields: Query<&mut Shield>, r_time: Res<Time>) {
+    for mut shield in q_shields.iter_mut() {
+        shield.since_hit += r_time.delta_seconds();
+        if shield.charges >= shield.max_charges || shield.since_hit < shield.recharge_delay {
+            continue;
+        }
+
+        shield.recharge_timer += r_time.delta_seconds();
+        if shield.recharge_timer >= shield.recharge_time {
+            shield.recharge_timer -= shield.recharge_time;
+            shield.charges += 1;
+        }
+    }
+}
+
+fn add_shield_rings(
+    mut cmds: Commands,
+    q_shields: Query<(Entity, &Shield), Added<Shield>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (e, shield) in q_shields.iter() {
+        let material = materials.add(Color::from(AQUA.with_alpha(0.5)));
+        let ring = cmds
+            .spawn((
+                Name::new("Shield Ring"),
+                ShieldRing {
+                    material: material.clone(),
+                },
+                MaterialMesh2dBundle {
+                    mesh: Mesh2dHandle(
+                        meshes.add(Annulus::new(shield.radius - 8.0, shield.radius)),
+                    ),
+                    material,
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                },
+            ))
+            .id();
+        cmds.entity(e).add_child(ring);
+    }
+}
+
+fn update_shield_rings(
+    q_shields: Query<&Shield>,
+    mut q_rings: Query<(&Parent, &ShieldRing, &mut Visibility)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (parent, ring, mut visibility) in q_rings.iter_mut() {
+        let Ok(shield) = q_shields.get(parent.get()) else {
+            continue;
+        };
+
+        *visibility = if shield.is_up() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        if let Some(material) = materials.get_mut(&ring.material) {
+            let charge = shield.charges as f32 / shield.max_charges.max(1) as f32;
+            material.color.set_alpha(0.15 + 0.45 * charge);
+        }
+    }
+}
@@ -2,6 +2,8 @@ pub use bevy::color::palettes::css::*;
 pub use bevy::ecs::system::RunSystemOnce;
 pub use bevy::ecs::world::Command;
 pub use bevy::prelude::*;
+// Both preludes below export these too, prefer Bevy's.
+pub use bevy::prelude::{AlphaMode, Real};
 
 pub use bevy_rapier2d::prelude::*;
 
@@ -10,8 +12,12 @@ pub use bevy_tweening::*;
 pub use bevy_hanabi::prelude::*;
 
 pub use crate::asteroids::{Asteroid, SpawnAsteroid};
-pub use crate::camera::*;
+pub use crate::camera::{PlayerCamera, PlayerCameraBundle};
 pub use crate::common::*;
-pub use crate::player::*;
+pub use crate::player::{KillPlayerOnTouch, OnPlayerDeath, Player, SpawnPlayer, PLAYER_COLORS};
 pub use crate::scenes::GameStates;
-pub use crate::spawner::*;
+pub use crate::shooter::Shooter;
+pub use crate::spawner::{AsteroidSpawner, UfoSpawner};
+pub use crate::ufo::{SpawnUfo, Ufo};
+pub use crate::viewport::VirtualViewport;
+pub use crate::weapons::WeaponDef;
@@ -1,22 +1,39 @@
+pub mod explosive;
+pub mod homing;
+pub mod modifiers;
+pub mod range;
+pub use explosive::*;
+pub use homing::*;
+pub use modifiers::*;
+pub use range::*;
+
 use bevy::{
     color::palettes::css::*,
+    ecs::query::QueryData,
     prelude::*,
     sprite::{MaterialMesh2dBundle, Mesh2dHandle},
 };
 use bevy_rapier2d::prelude::*;
 
-use crate::common::*;
+use crate::{common::*, weapons::WeaponDef};
 
 pub fn plugin(app: &mut App) {
+    app.add_plugins((
+        explosive::plugin,
+        homing::plugin,
+        modifiers::plugin,
+        range::plugin,
+    ));
+
     app.register_type::<Projectile>();
-    app.add_systems(PreUpdate, look_at_velocity);
     app.add_systems(
-        Update,
+        FixedUpdate,
         (
+            look_at_velocity.after(SteeringSet),
             resolve_projectile_collision,
             // debug_projectile_direction,
-            // look_at_velocity,
-        ),
+        )
+            .in_set(GameplaySet),
     );
 }
 
@@ -24,6 +41,23 @@ pub fn plugin(app: &mut App) {
 #[reflect(Component)]
 pub struct Projectile {
     damage: f32,
+    radius: f32,
+    /// Entity that fired the projectile.
+    owner: Option<Entity>,
+    /// Entities already hit, so piercing and bouncing never hit the same one twice.
+    hit: Vec<Entity>,
+    /// Set once the projectile is despawning, later collisions on the same step are ignored.
+    spent: bool,
+}
+impl Projectile {
+    pub fn new(damage: f32, radius: f32, owner: Option<Entity>) -> Self {
+        Self {
+            damage,
+            radius,
+            owner,
+            ..default()
+        }
+    }
 }
 
 #[derive(Bundle, Default)]
@@ -36,13 +70,36 @@ pub struct ProjectileBundle {
     pub sensor: Sensor,
     pub physics_events: ActiveEvents,
     pub locked_axis: LockedAxes,
+    pub faction: FactionBundle,
 }
 
 impl ProjectileBundle {
-    pub fn normal_projectile(
+    pub fn from_weapon(
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<ColorMaterial>>,
-        radius: f32,
+        weapon: &WeaponDef,
+        owner: Entity,
+        faction: Faction,
+        position: Vec2,
+        direction: Vec2,
+    ) -> Self {
+        let radius = weapon.projectile.radius;
+        Self::new(
+            Mesh2dHandle(meshes.add(Circle::new(radius))),
+            materials.add(weapon.projectile.color),
+            Projectile::new(weapon.damage, radius, Some(owner)),
+            weapon.projectile_lifetime(),
+            position,
+            direction * weapon.speed,
+        )
+        .with_faction(faction)
+    }
+
+    pub fn new(
+        mesh: Mesh2dHandle,
+        material: Handle<ColorMaterial>,
+        projectile: Projectile,
+        lifetime: f32,
         position: Vec2,
         velocity: Vec2,
     ) -> Self {
@@ -50,17 +107,9 @@ impl ProjectileBundle {
         xform.look_to(Vec3::Z, velocity.extend(0.0).normalize());
         Self {
             name: Name::new("Projectile"),
-            projectile: Projectile { damage: 1.0 },
-            lifetime: Lifetime::new(300.0),
-            mesh: MaterialMesh2dBundle {
-                mesh: Mesh2dHandle(meshes.add(Circle::new(radius))),
-                material: materials.add(Color::linear_rgb(1.0, 1.0, 1.0)),
-                transform: xform,
-                ..default()
-            },
             physics: PhysicsBundle {
                 rigidbody: RigidBody::KinematicVelocityBased,
-                collider: Collider::ball(radius),
+                collider: Collider::ball(projectile.radius),
                 gravity: GravityScale(0.0),
                 velocity: Velocity {
                     linvel: velocity,
@@ -69,25 +118,60 @@ impl ProjectileBundle {
                 restitution: Restitution::coefficient(0.7),
                 ..default()
             },
+            projectile,
+            lifetime: Lifetime::new(lifetime),
+            mesh: MaterialMesh2dBundle {
+                mesh,
+                material,
+                transform: xform,
+                ..default()
+            },
             locked_axis: LockedAxes::empty(),
             sensor: Sensor,
             physics_events: ActiveEvents::COLLISION_EVENTS,
+            faction: FactionBundle::projectile(Faction::Neutral),
             ..default()
         }
     }
+
+    pub fn with_faction(mut self, faction: Faction) -> Self {
+        self.faction = FactionBundle::projectile(faction);
+        self
+    }
+}
+
+#[derive(QueryData)]
+#[query_data(mutable)]
+struct ProjectileHitQuery {
+    entity: Entity,
+    projectile: &'static mut Projectile,
+    xform: &'static Transform,
+    velocity: &'static mut Velocity,
+    mesh: &'static Mesh2dHandle,
+    material: &'static Handle<ColorMaterial>,
+    pierce: Option<&'static mut Pierce>,
+    ricochet: Option<&'static mut Ricochet>,
+    split: Option<&'static mut SplitOnHit>,
+    explosive: Option<&'static Explosive>,
+    range: Option<&'static TravelRange>,
+    crit: Option<&'static Crit>,
+    faction: Option<&'static Faction>,
 }
 
 fn resolve_projectile_collision(
     mut cmds: Commands,
     mut collision_events: EventReader<CollisionEvent>,
-    q_projectiles: Query<(&Projectile, &Transform)>,
+    mut q_projectiles: Query<ProjectileHitQuery>,
+    r_rapier: Res<RapierContext>,
+    mut r_rng: ResMut<GameRng>,
+    factions: Factions,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
-            if let Ok((proj, xform)) = q_projectiles.get(*e1) {
-                hit_entity(&mut cmds, proj, xform, *e1, *e2);
-            } else if let Ok((proj, xform)) = q_projectiles.get(*e2) {
-                hit_entity(&mut cmds, proj, xform, *e2, *e1);
+            if let Ok(mut proj) = q_projectiles.get_mut(*e1) {
+                hit_entity(&mut cmds, &mut proj, *e2, &r_rapier, &mut r_rng, &factions);
+            } else if let Ok(mut proj) = q_projectiles.get_mut(*e2) {
+                hit_entity(&mut cmds, &mut proj, *e1, &r_rapier, &mut r_rng, &factions);
             }
         }
     }
@@ -119,17 +203,111 @@ fn look_at_velocity(mut q_projectiles: Query<(&Velocity, &mut Transform), With<P
 
 fn hit_entity(
     cmds: &mut Commands,
-    projectile: &Projectile,
-    xform: &Transform,
-    entity_source: Entity,
+    proj: &mut ProjectileHitQueryItem,
     entity_target: Entity,
+    rapier: &RapierContext,
+    rng: &mut GameRng,
+    factions: &Factions,
 ) {
-    let hit = HitData {
-        damage: projectile.damage,
-        point: xform.translation,
-        dir: xform.up(),
-        dealer: entity_source,
+    if proj.projectile.spent || proj.projectile.hit.contains(&entity_target) {
+        return;
+    }
+    // Goes through allies.
+    if !factions.faction_can_damage(proj.faction.copied(), entity_target) {
+        return;
+    }
+    proj.projectile.hit.push(entity_target);
+
+    let mut hit = HitData {
+        damage: proj.projectile.damage * proj.range.map_or(1.0, TravelRange::damage_multiplier),
+        point: proj.xform.translation,
+        dir: proj.xform.up(),
+        dealer: proj.entity,
+        damage_type: DamageType::Kinetic,
+        crit: false,
+        impulse: None,
+        ignore_invulnerability: false,
     };
+    if let Some(crit) = proj.crit {
+        crit.roll(&mut hit, rng);
+    }
     cmds.trigger_targets(OnHit(hit), entity_target);
-    cmds.entity(entity_source).despawn();
+
+    let position = proj.xform.translation.xy();
+    if let Some(split) = proj.split.as_mut().filter(|split| split.remaining > 0) {
+        split.remaining -= 1;
+        let speed = proj.velocity.linvel.length();
+        let forward = proj.velocity.linvel.normalize_or(proj.xform.up().xy());
+        for direction in split.directions(forward) {
+            let mut shard = Projectile::new(
+                proj.projectile.damage * split.damage,
+                proj.projectile.radius,
+                proj.projectile.owner,
+            );
+            shard.hit.push(entity_target);
+            let mut bundle = ProjectileBundle::new(
+                proj.mesh.clone(),
+                proj.material.clone(),
+                shard,
+                split.lifetime,
+                position,
+                direction * speed,
+            )
+            .with_faction(proj.faction.copied().unwrap_or_default());
+            bundle.mesh.transform.scale = Vec3::splat(split.scale);
+            let mut shard = cmds.spawn(bundle);
+            if let Some(crit) = proj.crit {
+                shard.insert(*crit);
+            }
+        }
+    }
+
+    if let Some(pierce) = proj.pierce.as_mut().filter(|pierce| pierce.remaining > 0) {
+        pierce.remaining -= 1;
+        return;
+    }
+
+    if let Some(ricochet) = proj
+        .ricochet
+        .as_mut()
+        .filter(|ricochet| ricochet.remaining > 0)
+    {
+        ricochet.remaining -= 1;
+        let normal = contact_normal(rapier, position, entity_target);
+        let velocity = proj.velocity.linvel;
+        if velocity.dot(normal) < 0.0 {
+            proj.velocity.linvel = velocity - 2.0 * velocity.dot(normal) * normal;
+        }
+        return;
+    }
+
+    proj.projectile.spent = true;
+    if let Some(explosive) = proj.explosive {
+        cmds.add(SpawnExplosion {
+            explosive: explosive.clone(),
+            position,
+            dealer: proj.entity,
+            owner: proj.projectile.owner,
+            faction: proj.faction.copied(),
+        });
+    }
+    cmds.entity(proj.entity).despawn();
+}
+
+/// Normal of the target's surface closest to the point, pointing out of the target.
+fn contact_normal(rapier: &RapierContext, point: Vec2, target: Entity) -> Vec2 {
+    let only_target = |e| e == target;
+    let filter = QueryFilter::default().predicate(&only_target);
+    rapier
+        .project_point(point, false, filter)
+        .and_then(|(_, projection)| {
+            let outwards = point - projection.point;
+            let normal = if projection.is_inside {
+                -outwards
+            } else {
+                outwards
+            };
+            normal.try_normalize()
+        })
+        .unwrap_or(Vec2::ZERO)
 }
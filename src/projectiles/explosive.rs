@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+use bevy_tweening::lens::{ColorMaterialColorLens, TransformScaleLens};
+
+use crate::{prelude::*, weapons::ExplosiveDef};
+
+use super::Projectile;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Explosive>();
+
+    app.observe(explode_on_expiry);
+}
+
+/// Blows up when the projectile is spent or expires, hurting everything around.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Explosive {
+    pub radius: f32,
+    pub damage: f32,
+    /// Fraction of the damage and impulse left at the edge of the radius.
+    pub falloff: f32,
+    /// Impulse pushing things out of the center.
+    pub impulse: f32,
+    /// Fraction of the damage dealt to whoever fired it, zero leaves them untouched.
+    pub self_damage: f32,
+}
+impl Explosive {
+    pub fn new(def: &ExplosiveDef) -> Self {
+        Self {
+            radius: def.radius,
+            damage: def.damage,
+            falloff: def.falloff,
+            impulse: def.impulse,
+            self_damage: def.self_damage,
+        }
+    }
+}
+
+pub struct SpawnExplosion {
+    pub explosive: Explosive,
+    pub position: Vec2,
+    pub dealer: Entity,
+    /// Entity that fired the explosive, for the self damage rules.
+    pub owner: Option<Entity>,
+    pub faction: Option<Faction>,
+}
+impl Command for SpawnExplosion {
+    fn apply(self, world: &mut World) {
+        world.run_system_once_with(self, spawn_explosion);
+    }
+}
+fn spawn_explosion(
+    spawn: In<SpawnExplosion>,
+    mut cmds: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    q_targets: Query<&GlobalTransform>,
+    factions: Factions,
+    r_rapier: Res<RapierContext>,
+) {
+    let SpawnExplosion {
+        explosive,
+        position,
+        dealer,
+        owner,
+        faction,
+    } = spawn.0;
+
+    let mut targets = Vec::new();
+    r_rapier.intersections_with_shape(
+        position,
+        0.0,
+        &Collider::ball(explosive.radius),
+        QueryFilter::default().exclude_sensors(),
+        |e| {
+            targets.push(e);
+            true
+        },
+    );
+
+    for target in targets {
+        // Self damage has its own rule, whatever the factions say.
+        let self_hit = owner == Some(target);
+        if self_hit && explosive.self_damage <= 0.0 {
+            continue;
+        }
+        if !self_hit && !factions.faction_can_damage(faction, target) {
+            continue;
+        }
+
+        // Physics can still list colliders of entities despawned earlier this step.
+        let Ok(target_xform) = q_targets.get(target) else {
+            continue;
+        };
+        let target_pos = target_xform.translation().xy();
+        let offset = target_pos - position;
+        let distance = (offset.length() / explosive.radius).min(1.0);
+        let mut strength = 1.0 + (explosive.falloff - 1.0) * distance;
+        if self_hit {
+            strength *= explosive.self_damage;
+        }
+
+        let dir = Dir2::new(offset).unwrap_or(Dir2::Y);
+        let hit = HitData {
+            point: target_pos.extend(0.0),
+            dir: Dir3::new(dir.extend(0.0)).unwrap_or(Dir3::Y),
+            dealer,
+            damage: explosive.damage * strength,
+            damage_type: DamageType::Explosive,
+            crit: false,
+            // Pushed by the knockback observer, once.
+            impulse: Some(explosive.impulse * strength),
+            ignore_invulnerability: false,
+        };
+        cmds.trigger_targets(OnHit(hit), target);
+    }
+
+    let duration = Duration::from_secs_f32(0.35);
+    let color = Color::from(ORANGE);
+    let material = materials.add(color);
+    cmds.spawn((
+        Name::new("Explosion"),
+        Lifetime::new(duration.as_secs_f32()),
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Circle::new(explosive.radius))),
+            material,
+            transform: Transform::from_translation(position.extend(0.5)),
+            ..default()
+        },
+        Animator::new(Tween::new(
+            EaseFunction::QuadraticOut,
+            duration,
+            TransformScaleLens {
+                start: Vec3::splat(0.2),
+                end: Vec3::ONE,
+            },
+        )),
+        AssetAnimator::new(Tween::new(
+            EaseFunction::QuadraticIn,
+            duration,
+            ColorMaterialColorLens {
+                start: color.with_alpha(0.8),
+                end: color.with_alpha(0.0),
+            },
+        )),
+    ));
+}
+
+fn explode_on_expiry(
+    e_expired: Trigger<OnExpired>,
+    mut cmds: Commands,
+    q_explosives: Query<(&Explosive, &Projectile, &GlobalTransform, Option<&Faction>)>,
+) {
+    if let Ok((explosive, projectile, xform, faction)) = q_explosives.get(e_expired.entity()) {
+        cmds.add(SpawnExplosion {
+            explosive: explosive.clone(),
+            position: xform.translation().xy(),
+            dealer: e_expired.entity(),
+            owner: projectile.owner,
+            faction: faction.copied(),
+        });
+    }
+}
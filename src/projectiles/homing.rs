@@ -0,0 +1,104 @@
+use crate::{prelude::*, weapons::HomingDef};
+
+use super::look_at_velocity;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Homing>();
+
+    app.add_systems(
+        FixedUpdate,
+        (
+            acquire_homing_targets.before(SteeringSet),
+            burn_homing_fuel.after(SteeringSet),
+        )
+            .before(look_at_velocity)
+            .in_set(GameplaySet),
+    );
+}
+
+/// Points the projectile's [`Seek`] at the nearest living [`Health`] in front of it.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Homing {
+    pub seek_radius: f32,
+    /// Half angle in radians of the cone targets are picked from.
+    pub seek_angle: f32,
+    /// Seconds of steering left, the projectile expires when it runs out.
+    pub fuel: f32,
+}
+impl Homing {
+    pub fn new(def: &HomingDef) -> Self {
+        Self {
+            seek_radius: def.seek_radius,
+            seek_angle: def.seek_angle.to_radians(),
+            fuel: def.fuel,
+        }
+    }
+}
+
+fn acquire_homing_targets(
+    mut q_homing: Query<(Entity, &GlobalTransform, &Velocity, &Homing, &mut Seek)>,
+    q_targets: Query<(&GlobalTransform, &Health)>,
+    factions: Factions,
+    r_rapier: Res<RapierContext>,
+) {
+    for (e, xform, velocity, homing, mut seek) in q_homing.iter_mut() {
+        let is_target = |target| {
+            factions.can_damage(e, target)
+                && q_targets
+                    .get(target)
+                    .is_ok_and(|(_, health)| !health.is_dead())
+        };
+
+        // Keep chasing the current target until it dies.
+        if let Some(SteeringTarget::Entity(target)) = seek.target {
+            if is_target(target) {
+                continue;
+            }
+        }
+
+        let position = xform.translation().xy();
+        let forward = velocity.linvel.normalize_or(xform.up().xy());
+        let mut nearest: Option<(Entity, f32)> = None;
+        r_rapier.intersections_with_shape(
+            position,
+            0.0,
+            &Collider::ball(homing.seek_radius),
+            QueryFilter::default()
+                .exclude_sensors()
+                .predicate(&is_target),
+            |e| {
+                let Ok((target_xform, _)) = q_targets.get(e) else {
+                    return true;
+                };
+                let to_target = target_xform.translation().xy() - position;
+                let distance = to_target.length_squared();
+                let in_cone = to_target == Vec2::ZERO
+                    || forward.angle_between(to_target).abs() <= homing.seek_angle;
+                let closer = match nearest {
+                    Some((_, nearest)) => distance < nearest,
+                    None => true,
+                };
+                if in_cone && closer {
+                    nearest = Some((e, distance));
+                }
+                true
+            },
+        );
+        seek.target = nearest.map(|(e, _)| SteeringTarget::Entity(e));
+    }
+}
+
+fn burn_homing_fuel(
+    mut cmds: Commands,
+    mut q_homing: Query<(Entity, &mut Homing)>,
+    r_time: Res<Time>,
+) {
+    for (e, mut homing) in q_homing.iter_mut() {
+        homing.fuel -= r_time.delta_seconds();
+        if homing.fuel <= 0.0 {
+            cmds.trigger_targets(OnExpired, e);
+            cmds.entity(e).despawn_recursive();
+        }
+    }
+}
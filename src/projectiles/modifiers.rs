@@ -0,0 +1,96 @@
+use bevy::ecs::system::EntityCommands;
+
+use crate::{prelude::*, weapons::SplitDef};
+
+use super::{Explosive, Homing, TravelRange};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Pierce>()
+        .register_type::<Ricochet>()
+        .register_type::<SplitOnHit>();
+}
+
+/// Goes through targets instead of despawning on them.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Pierce {
+    pub remaining: u32,
+}
+
+/// Bounces off targets it can't pierce, reflecting around the contact normal.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Ricochet {
+    pub remaining: u32,
+}
+
+/// Bursts into smaller projectiles fanning out from the hit.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct SplitOnHit {
+    pub remaining: u32,
+    pub shards: u32,
+    /// Angle in radians between the outermost shards.
+    pub spread: f32,
+    /// Fraction of the damage each shard deals.
+    pub damage: f32,
+    /// Size of the shards relative to the projectile.
+    pub scale: f32,
+    pub lifetime: f32,
+}
+impl SplitOnHit {
+    pub fn new(def: &SplitDef) -> Self {
+        Self {
+            remaining: 1,
+            shards: def.shards,
+            spread: def.spread.to_radians(),
+            damage: def.damage,
+            scale: def.scale,
+            lifetime: def.lifetime,
+        }
+    }
+
+    /// Direction of each shard, fanned out around `forward`.
+    pub fn directions(&self, forward: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        let count = self.shards.max(1);
+        (0..count).map(move |i| {
+            let angle = if count > 1 {
+                -self.spread / 2.0 + self.spread * i as f32 / (count - 1) as f32
+            } else {
+                0.0
+            };
+            Vec2::from_angle(angle).rotate(forward)
+        })
+    }
+}
+
+/// Adds the behaviours the weapon gives its projectiles.
+pub fn add_weapon_modifiers(projectile: &mut EntityCommands, weapon: &WeaponDef) {
+    projectile.insert(TravelRange::new(weapon));
+    if weapon.crit_chance > 0.0 {
+        projectile.insert(Crit::new(weapon.crit_chance, weapon.crit_multiplier));
+    }
+    if let Some(homing) = &weapon.homing {
+        projectile.insert((
+            Homing::new(homing),
+            Seek::default(),
+            Steering::turning(weapon.speed, homing.turn_rate.to_radians()),
+        ));
+    }
+    if weapon.pierce > 0 {
+        projectile.insert(Pierce {
+            remaining: weapon.pierce,
+        });
+    }
+    if weapon.ricochet > 0 {
+        projectile.insert(Ricochet {
+            remaining: weapon.ricochet,
+        });
+    }
+    if let Some(split) = &weapon.split {
+        projectile.insert(SplitOnHit::new(split));
+    }
+    if let Some(explosive) = &weapon.explosive {
+        projectile.insert(Explosive::new(explosive));
+    }
+}
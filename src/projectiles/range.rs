@@ -0,0 +1,54 @@
+use crate::{prelude::*, weapons::WeaponDef};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<TravelRange>();
+
+    app.add_systems(FixedUpdate, expire_out_of_range.in_set(GameplaySet));
+}
+
+/// Despawns the projectile once it has travelled its range, whatever path it took.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct TravelRange {
+    pub max: f32,
+    pub travelled: f32,
+    /// Distance the damage starts dropping at, linearly until `max`.
+    pub falloff_start: Option<f32>,
+    /// Fraction of the damage left at `max`.
+    pub min_damage: f32,
+}
+impl TravelRange {
+    pub fn new(weapon: &WeaponDef) -> Self {
+        Self {
+            max: weapon.range,
+            travelled: 0.0,
+            falloff_start: weapon.falloff.as_ref().map(|falloff| falloff.start),
+            min_damage: weapon
+                .falloff
+                .as_ref()
+                .map_or(1.0, |falloff| falloff.min_damage),
+        }
+    }
+
+    pub fn damage_multiplier(&self) -> f32 {
+        let Some(start) = self.falloff_start else {
+            return 1.0;
+        };
+        let t = ((self.travelled - start) / (self.max - start).max(f32::EPSILON)).clamp(0.0, 1.0);
+        1.0 + (self.min_damage - 1.0) * t
+    }
+}
+
+fn expire_out_of_range(
+    mut cmds: Commands,
+    r_time: Res<Time>,
+    mut q_ranges: Query<(Entity, &mut TravelRange, &Velocity)>,
+) {
+    for (e, mut range, velocity) in q_ranges.iter_mut() {
+        range.travelled += velocity.linvel.length() * r_time.delta_seconds();
+        if range.travelled >= range.max {
+            cmds.trigger_targets(OnExpired, e);
+            cmds.entity(e).despawn_recursive();
+        }
+    }
+}
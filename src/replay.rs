@@ -0,0 +1,239 @@
+use std::{fmt, fs, io, path::PathBuf, time::Duration};
+
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    input::{InputSet, PlayerInputs},
+    prelude::*,
+};
+
+pub const REPLAY_VERSION: u32 = 2;
+
+/// Pass `--record <path>` to save the session, `--replay <path>` to play one back.
+pub fn plugin(app: &mut App) {
+    if let Some(path) = arg_value("--replay") {
+        match Replay::load(&path) {
+            Ok(replay) => {
+                info!("Playing replay {:?} with seed {}", path, replay.seed);
+                app.insert_resource(ReplayPlayer::new(replay));
+            }
+            Err(err) => error!("Couldn't load replay {:?}: {err}", path),
+        }
+    } else if let Some(path) = arg_value("--record") {
+        app.insert_resource(ReplayRecorder::new(path));
+    }
+
+    app.add_systems(Startup, (start_recording, start_playback));
+    app.add_systems(
+        FixedPreUpdate,
+        (
+            play_input.run_if(resource_exists::<ReplayPlayer>),
+            record_input.run_if(resource_exists::<ReplayRecorder>),
+        )
+            .chain()
+            .in_set(InputSet::Override),
+    );
+    app.add_systems(
+        Last,
+        (
+            advance_playback_frame.run_if(resource_exists::<ReplayPlayer>),
+            (record_frame, save_recording.run_if(on_event::<AppExit>()))
+                .chain()
+                .run_if(resource_exists::<ReplayRecorder>),
+        ),
+    );
+    app.add_systems(
+        OnExit(GameStates::Match),
+        save_recording.run_if(resource_exists::<ReplayRecorder>),
+    );
+}
+
+/// Everything needed to simulate a session again: the seed, how many fixed steps ran on
+/// each frame and the input of every local player on each of those steps.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub frame_steps: Vec<u32>,
+    pub inputs: Vec<PlayerInputs>,
+}
+impl Replay {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            ..default()
+        }
+    }
+
+    pub fn load(path: &PathBuf) -> Result<Self, ReplayError> {
+        let replay: Self = ron::from_str(&fs::read_to_string(path)?)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::Version(replay.version));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<(), ReplayError> {
+        fs::write(path, ron::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Format(ron::Error),
+    Version(u32),
+}
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "{err}"),
+            ReplayError::Format(err) => write!(f, "{err}"),
+            ReplayError::Version(version) => write!(
+                f,
+                "replay version {version} isn't supported, expected {REPLAY_VERSION}"
+            ),
+        }
+    }
+}
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
+impl From<ron::Error> for ReplayError {
+    fn from(err: ron::Error) -> Self {
+        ReplayError::Format(err)
+    }
+}
+impl From<ron::error::SpannedError> for ReplayError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        ReplayError::Format(err.code)
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub replay: Replay,
+    frame_steps: u32,
+}
+impl ReplayRecorder {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            replay: Replay::new(0),
+            frame_steps: 0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    step: usize,
+    frame: usize,
+    previous_strategy: TimeUpdateStrategy,
+}
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            step: 0,
+            frame: 0,
+            previous_strategy: TimeUpdateStrategy::default(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.frame_steps.len()
+    }
+}
+
+fn arg_value(name: &str) -> Option<PathBuf> {
+    std::env::args()
+        .skip_while(|arg| arg != name)
+        .nth(1)
+        .map(PathBuf::from)
+}
+
+fn start_recording(recorder: Option<ResMut<ReplayRecorder>>, r_rng: Res<GameRng>) {
+    if let Some(mut recorder) = recorder {
+        recorder.replay.seed = r_rng.seed();
+    }
+}
+
+fn start_playback(
+    player: Option<ResMut<ReplayPlayer>>,
+    mut r_rng: ResMut<GameRng>,
+    mut r_strategy: ResMut<TimeUpdateStrategy>,
+    r_fixed: Res<Time<Fixed>>,
+) {
+    if let Some(mut player) = player {
+        r_rng.reseed(player.replay.seed);
+        player.previous_strategy = std::mem::take(&mut *r_strategy);
+        *r_strategy = frame_strategy(&player, r_fixed.timestep());
+    }
+}
+
+fn play_input(mut player: ResMut<ReplayPlayer>, mut r_inputs: ResMut<PlayerInputs>) {
+    if let Some(inputs) = player.replay.inputs.get(player.step) {
+        *r_inputs = inputs.clone();
+    } else {
+        r_inputs.0.iter_mut().for_each(|input| *input = default());
+    }
+    player.step += 1;
+}
+
+/// Makes the next frame simulate exactly as many fixed steps as it did while recording.
+fn advance_playback_frame(
+    mut cmds: Commands,
+    mut player: ResMut<ReplayPlayer>,
+    mut r_strategy: ResMut<TimeUpdateStrategy>,
+    r_fixed: Res<Time<Fixed>>,
+    r_virtual: Res<Time<Virtual>>,
+) {
+    // No steps run while paused, hold the frame until the playback resumes.
+    if r_virtual.is_paused() {
+        return;
+    }
+
+    player.frame += 1;
+    if player.is_finished() {
+        info!("Replay finished");
+        *r_strategy = std::mem::take(&mut player.previous_strategy);
+        cmds.remove_resource::<ReplayPlayer>();
+    } else {
+        *r_strategy = frame_strategy(&player, r_fixed.timestep());
+    }
+}
+
+fn frame_strategy(player: &ReplayPlayer, timestep: Duration) -> TimeUpdateStrategy {
+    let steps = player
+        .replay
+        .frame_steps
+        .get(player.frame)
+        .copied()
+        .unwrap_or(0);
+    TimeUpdateStrategy::ManualDuration(timestep * steps)
+}
+
+fn record_input(mut recorder: ResMut<ReplayRecorder>, r_inputs: Res<PlayerInputs>) {
+    recorder.replay.inputs.push(r_inputs.clone());
+    recorder.frame_steps += 1;
+}
+
+fn record_frame(mut recorder: ResMut<ReplayRecorder>) {
+    let steps = std::mem::take(&mut recorder.frame_steps);
+    recorder.replay.frame_steps.push(steps);
+}
+
+fn save_recording(recorder: Res<ReplayRecorder>) {
+    match recorder.replay.save(&recorder.path) {
+        Ok(()) => info!("Replay saved to {:?}", recorder.path),
+        Err(err) => error!("Couldn't save replay to {:?}: {err}", recorder.path),
+    }
+}
@@ -1,17 +1,29 @@
 use std::time::Duration;
 
-
-use crate::prelude::*;
+use crate::{
+    input::InputAction,
+    player::{spawn_joined_players, Respawning, SpawnedPlayers},
+    prelude::*,
+};
 
 pub fn plugin(app: &mut App) {
     app.register_type::<GameStates>();
 
-    app.insert_state(GameStates::Menu);
+    app.insert_state(GameStates::Menu)
+        .enable_state_scoped_entities::<GameStates>();
 
     app.add_systems(Startup, game_setup);
     app.add_systems(OnEnter(GameStates::Match), match_setup);
-
-    app.observe(on_player_death);
+    app.add_systems(OnExit(GameStates::Match), unpause);
+    app.add_systems(Update, toggle_pause.run_if(in_state(GameStates::Match)));
+    app.add_systems(
+        FixedUpdate,
+        check_match_over
+            .run_if(in_state(GameStates::Match))
+            // Ships of new players only show up once the spawn commands are applied.
+            .before(spawn_joined_players)
+            .in_set(GameplaySet),
+    );
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash, States, Reflect)]
@@ -24,23 +36,44 @@ fn game_setup(mut cmds: Commands) {
     let mut player_camera = PlayerCameraBundle::new();
     player_camera.camera.projection.scale = 5.0;
     cmds.spawn(player_camera);
-
-    cmds.add(SpawnPlayer);
 }
 
-fn match_setup(mut cmds: Commands) {
+fn match_setup(mut cmds: Commands, mut r_rng: ResMut<GameRng>) {
+    // Every match starts from the same streams so a seed reproduces the whole run.
+    r_rng.reset();
+
     cmds.spawn((
         Name::new("Spawner"),
         StateScoped(GameStates::Match),
         AsteroidSpawner {
             timer: Timer::new(Duration::from_secs_f32(5.0), TimerMode::Repeating),
         },
+        UfoSpawner::default(),
     ));
 }
 
-fn on_player_death(
-    e_player_dead: Trigger<OnPlayerDeath>,
+fn toggle_pause(r_actions: Res<ButtonInput<InputAction>>, mut r_time: ResMut<Time<Virtual>>) {
+    if r_actions.just_pressed(InputAction::Pause) {
+        if r_time.is_paused() {
+            r_time.unpause();
+        } else {
+            r_time.pause();
+        }
+    }
+}
+
+fn unpause(mut r_time: ResMut<Time<Virtual>>) {
+    r_time.unpause();
+}
+
+/// The match is over once every player that got a ship has lost it and has no lives left.
+fn check_match_over(
+    q_players: Query<(), With<Player>>,
+    q_respawns: Query<(), With<Respawning>>,
+    r_spawned: Res<SpawnedPlayers>,
     mut r_state: ResMut<NextState<GameStates>>,
 ) {
-    r_state.set(GameStates::Menu);
+    if r_spawned.0 > 0 && q_players.is_empty() && q_respawns.is_empty() {
+        r_state.set(GameStates::Menu);
+    }
 }
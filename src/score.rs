@@ -8,20 +8,41 @@ use bevy::{
 };
 use bevy_rapier2d::prelude::*;
 
-use crate::{common::*, scenes::GameStates};
+use crate::{common::*, player::Player, scenes::GameStates};
 
 pub fn plugin(app: &mut App) {
     app.insert_resource(PlayerCurrency(0));
+    app.init_resource::<CurrencyMode>();
+    app.init_resource::<PlayerWallets>();
     app.register_type::<PlayerCurrency>();
+    app.register_type::<PlayerWallets>();
     app.register_type::<Money>();
 
-    app.observe(on_pickup_money);
+    app.observe(drop_money_on_death).observe(on_pickup_money);
 }
 
+/// Money shared by every player, used with [`CurrencyMode::Shared`].
 #[derive(Resource, Reflect, Default)]
 #[reflect(Resource)]
 pub struct PlayerCurrency(pub u32);
 
+/// Money of each player by index, used with [`CurrencyMode::PerPlayer`].
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct PlayerWallets(pub Vec<u32>);
+impl PlayerWallets {
+    pub fn total(&self) -> u32 {
+        self.0.iter().sum()
+    }
+}
+
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurrencyMode {
+    #[default]
+    Shared,
+    PerPlayer,
+}
+
 /// Amount of money to be dropped on death.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
@@ -47,8 +68,9 @@ fn spawn_money(
     mut cmds: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut r_rng: ResMut<GameRng>,
 ) {
-    let mut rng = SimpleRng::default();
+    let rng = r_rng.stream(RngStream::Money);
     for i in 0..spawn.0.money {
         cmds.spawn((
             Money(1),
@@ -63,27 +85,55 @@ fn spawn_money(
             RigidBody::Dynamic,
             Sensor,
             Collider::cuboid(10.0, 10.0),
+            // Never in the way of anything, the player's filter leaves pickups out too.
+            // Receivers find them with a shape query instead, see `detect_pickups`.
+            CollisionGroups::new(PICKUP_GROUP, PLAYER_GROUP),
             Damping {
                 linear_damping: 1.0,
                 ..default()
             },
             GravityScale(0.0),
             Velocity {
-                linvel: rng.circle()
-                    * rng.value_range(spawn.radial_force.start, spawn.radial_force.end),
+                linvel: rng.annulus(spawn.radial_force.start, spawn.radial_force.end),
                 angvel: rng.value_range(-10.0, 10.0),
             },
         ));
     }
 }
 
+fn drop_money_on_death(
+    e_death: Trigger<OnDeath>,
+    mut cmds: Commands,
+    q_drops: Query<(&MoneyDrop, &GlobalTransform)>,
+) {
+    if let Ok((money, xform)) = q_drops.get(e_death.entity()) {
+        cmds.add(SpawnMoney {
+            money: money.0,
+            position: xform.translation().xy(),
+            radial_force: 150.0..300.0,
+        });
+    }
+}
+
 fn on_pickup_money(
     e_pickup: Trigger<OnPickedUp>,
     q_score: Query<&Money>,
+    q_players: Query<&Player>,
+    r_mode: Res<CurrencyMode>,
     mut r_score: ResMut<PlayerCurrency>,
+    mut r_wallets: ResMut<PlayerWallets>,
 ) {
-    if let Ok(score) = q_score.get(e_pickup.entity()) {
-        r_score.0 += score.0;
-        println!("New score: {:?}", r_score.0);
+    let Ok(score) = q_score.get(e_pickup.entity()) else {
+        return;
+    };
+
+    match (*r_mode, q_players.get(e_pickup.event().receiver_entity)) {
+        (CurrencyMode::PerPlayer, Ok(player)) => {
+            if r_wallets.0.len() <= player.index {
+                r_wallets.0.resize(player.index + 1, 0);
+            }
+            r_wallets.0[player.index] += score.0;
+        }
+        _ => r_score.0 += score.0,
     }
 }
@@ -0,0 +1,61 @@
+use std::{fs, path::PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::prelude::*;
+
+pub const SETTINGS_DIR: &str = "settings";
+
+/// Resource persisted as a RON file in the settings folder.
+pub trait SettingsFile: Resource + Serialize + DeserializeOwned + Default {
+    const FILE_NAME: &'static str;
+
+    fn path() -> PathBuf {
+        PathBuf::from(SETTINGS_DIR).join(Self::FILE_NAME)
+    }
+
+    /// Falls back to the defaults if the file is missing or can't be read.
+    fn load_or_default() -> Self {
+        let path = Self::path();
+        match fs::read_to_string(&path) {
+            Ok(text) => ron::from_str(&text).unwrap_or_else(|err| {
+                warn!("Couldn't parse settings {:?}, using defaults: {err}", path);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) {
+        let path = Self::path();
+        let result = ron::ser::to_string_pretty(self, default())
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                fs::create_dir_all(SETTINGS_DIR)
+                    .and_then(|_| fs::write(&path, text))
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            error!("Couldn't save settings {:?}: {err}", path);
+        }
+    }
+}
+
+pub trait SettingsAppExt {
+    /// Loads the settings resource and saves it back whenever it changes.
+    fn init_settings<T: SettingsFile>(&mut self) -> &mut Self;
+}
+impl SettingsAppExt for App {
+    fn init_settings<T: SettingsFile>(&mut self) -> &mut Self {
+        self.insert_resource(T::load_or_default());
+        self.add_systems(
+            Last,
+            save_settings::<T>.run_if(resource_changed::<T>.and_then(not(resource_added::<T>))),
+        );
+        self
+    }
+}
+
+fn save_settings<T: SettingsFile>(r_settings: Res<T>) {
+    r_settings.save();
+}
@@ -1,49 +1,97 @@
+use crate::common::{Faction, GameplaySet, Knockback};
 use crate::projectiles::*;
+use crate::weapons::WeaponDef;
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::*;
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Shooter>();
 
-    app.add_systems(Update, (shooter_fire));
+    app.add_systems(FixedUpdate, shooter_fire.in_set(GameplaySet));
 }
 
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
 pub struct Shooter {
     pub enabled: bool,
+    pub weapon: Handle<WeaponDef>,
     pub shoot_timer: f32,
-    pub shoot_delay: f32,
+    /// Shots left in the current burst.
+    pub burst_left: u32,
+    pub burst_timer: f32,
+    pub muzzle_index: usize,
+}
+impl Shooter {
+    pub fn new(weapon: Handle<WeaponDef>) -> Self {
+        Self {
+            weapon,
+            ..default()
+        }
+    }
 }
 
-fn shooter_fire(
+pub(crate) fn shooter_fire(
     mut cmds: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut gizmos: Gizmos,
     mut materials: ResMut<Assets<ColorMaterial>>,
     time: Res<Time>,
-    mut q_shooters: Query<(&GlobalTransform, &mut Shooter)>,
+    r_weapons: Res<Assets<WeaponDef>>,
+    mut q_shooters: Query<(
+        Entity,
+        &GlobalTransform,
+        &mut Shooter,
+        Option<&mut Knockback>,
+    )>,
+    q_factions: Query<&Faction>,
 ) {
-    for (transform, mut shooter) in q_shooters.iter_mut() {
-        if shooter.shoot_timer < shooter.shoot_delay {
+    for (e, transform, mut shooter, mut knockback) in q_shooters.iter_mut() {
+        let faction = q_factions.get(e).copied().unwrap_or_default();
+        // Beams fire continuously, see `weapons::beam`.
+        let Some(weapon) = r_weapons
+            .get(&shooter.weapon)
+            .filter(|weapon| weapon.beam.is_none())
+        else {
+            continue;
+        };
+
+        let shoot_delay = weapon.shot_delay();
+        if shooter.shoot_timer < shoot_delay {
             shooter.shoot_timer += time.delta_seconds();
         }
 
-        while shooter.enabled && shooter.shoot_timer >= shooter.shoot_delay {
-            shooter.shoot_timer -= shooter.shoot_delay;
-
-            let radius = 10.0;
-            let spawn_velocity = (transform.up().as_vec3() * 2000.0).xy();
-            let spawn_position = (transform.translation() + transform.up().as_vec3() * 75.0).xy();
-            let projectile = ProjectileBundle::normal_projectile(
-                &mut meshes,
-                &mut materials,
-                radius,
-                spawn_position,
-                spawn_velocity,
-            );
-
-            cmds.spawn(projectile);
+        while shooter.enabled && shooter.shoot_timer >= shoot_delay {
+            shooter.shoot_timer -= shoot_delay;
+            shooter.burst_left += weapon.burst_size.max(1);
+        }
+
+        if shooter.burst_left == 0 {
+            // The first shot of the next burst goes out right away.
+            shooter.burst_timer = weapon.burst_delay;
+            continue;
+        }
+
+        shooter.burst_timer += time.delta_seconds();
+        while shooter.burst_left > 0 && shooter.burst_timer >= weapon.burst_delay {
+            shooter.burst_timer -= weapon.burst_delay;
+            shooter.burst_left -= 1;
+
+            let muzzle = weapon.muzzle(shooter.muzzle_index);
+            shooter.muzzle_index = shooter.muzzle_index.wrapping_add(1);
+            let spawn_position = transform.transform_point(muzzle.extend(0.0)).xy();
+            for direction in weapon.spread_directions(transform.up().xy()) {
+                let mut projectile = cmds.spawn(ProjectileBundle::from_weapon(
+                    &mut meshes,
+                    &mut materials,
+                    weapon,
+                    e,
+                    faction,
+                    spawn_position,
+                    direction,
+                ));
+                add_weapon_modifiers(&mut projectile, weapon);
+            }
+            if let Some(knockback) = knockback.as_mut().filter(|_| weapon.recoil > 0.0) {
+                knockback.push(-transform.up().xy() * weapon.recoil);
+            }
         }
     }
 }
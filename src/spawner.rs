@@ -1,11 +1,16 @@
-use crate::prelude::*;
+use std::f32::consts::PI;
+
+use crate::{prelude::*, ufo::UfoKind};
 
 pub fn plugin(app: &mut App) {
     app.register_type::<AsteroidSpawner>();
+    app.register_type::<UfoSpawner>();
 
     app.add_systems(
-        Update,
-        run_asteroid_spawner.run_if(in_state(GameStates::Match)),
+        FixedUpdate,
+        (run_asteroid_spawner, run_ufo_spawner)
+            .run_if(in_state(GameStates::Match))
+            .in_set(GameplaySet),
     );
 }
 
@@ -17,25 +22,115 @@ pub struct AsteroidSpawner {
 
 fn run_asteroid_spawner(
     mut cmds: Commands,
-    q_wnd: Query<&Window>,
-    q_player: Query<(&GlobalTransform), With<Player>>,
+    q_players: Query<(&Player, &GlobalTransform)>,
     mut q_spawners: Query<&mut AsteroidSpawner>,
     r_time: Res<Time>,
+    mut r_rng: ResMut<GameRng>,
+    r_viewport: Res<VirtualViewport>,
 ) {
-    let wnd = q_wnd.single();
-    let player = q_player.single();
-    let mut rng = SimpleRng::default();
+    // Sorted so the target picked from the rng doesn't depend on the query order.
+    let mut players: Vec<_> = q_players.iter().collect();
+    players.sort_by_key(|(player, _)| player.index);
+    if players.is_empty() {
+        return;
+    }
+    let rng = r_rng.stream(RngStream::Spawner);
 
     for mut spawner in q_spawners.iter_mut() {
         spawner.timer.tick(r_time.delta());
         if spawner.timer.finished() {
-            let spawn_pos = player.translation().xy() + rng.circle() * wnd.size().x * 2.0;
+            let target = if players.len() == 1 {
+                players[0].1.translation().xy()
+            } else {
+                let index = rng.value_range(0.0, players.len() as f32) as usize;
+                players[index.min(players.len() - 1)].1.translation().xy()
+            };
+            let spawn_pos = target + rng.circle() * r_viewport.size.x * 2.0;
             cmds.add(SpawnAsteroid {
                 depth: 2,
                 position: spawn_pos,
-                velocity: (player.translation().xy() - spawn_pos).normalize()
-                    * rng.value_range(100.0, 350.0),
+                velocity: (target - spawn_pos).normalize() * rng.value_range(100.0, 350.0),
             });
         }
     }
 }
+
+/// Sends saucers in more and more often as the match goes on, with smaller and deadlier ones
+/// showing up later.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct UfoSpawner {
+    pub timer: Timer,
+    /// Seconds since the match started.
+    pub elapsed: f32,
+    /// Seconds it takes to reach full difficulty.
+    pub ramp_up: f32,
+    pub max_alive: usize,
+}
+impl Default for UfoSpawner {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(20.0, TimerMode::Once),
+            elapsed: 0.0,
+            ramp_up: 180.0,
+            max_alive: 2,
+        }
+    }
+}
+impl UfoSpawner {
+    /// Between 0 at the start of the match and 1 once fully ramped up.
+    pub fn difficulty(&self) -> f32 {
+        (self.elapsed / self.ramp_up.max(f32::EPSILON)).min(1.0)
+    }
+}
+
+fn run_ufo_spawner(
+    mut cmds: Commands,
+    q_players: Query<(&Player, &GlobalTransform)>,
+    q_ufos: Query<(), With<Ufo>>,
+    mut q_spawners: Query<&mut UfoSpawner>,
+    r_time: Res<Time>,
+    mut r_rng: ResMut<GameRng>,
+) {
+    let mut players: Vec<_> = q_players.iter().collect();
+    players.sort_by_key(|(player, _)| player.index);
+    if players.is_empty() {
+        return;
+    }
+    let rng = r_rng.stream(RngStream::Enemies);
+
+    for mut spawner in q_spawners.iter_mut() {
+        spawner.elapsed += r_time.delta_seconds();
+        if !spawner.timer.tick(r_time.delta()).finished() {
+            continue;
+        }
+        let difficulty = spawner.difficulty();
+        spawner.timer = Timer::from_seconds(20.0.lerp(8.0, difficulty), TimerMode::Once);
+        if q_ufos.iter().len() >= spawner.max_alive {
+            continue;
+        }
+
+        let index = rng.value_range(0.0, players.len() as f32) as usize;
+        let target = players[index.min(players.len() - 1)].1.translation().xy();
+        let kind = if rng.value() < 0.1.lerp(0.7, difficulty) {
+            UfoKind::Small
+        } else {
+            UfoKind::Large
+        };
+        let inaccuracy = match kind {
+            UfoKind::Large => PI,
+            UfoKind::Small => 25f32.lerp(2.0, difficulty).to_radians(),
+        };
+        // Comes in from one side and leaves through the other.
+        let heading = if rng.value() < 0.5 { -1.0 } else { 1.0 };
+        let crossing = 8000.0;
+        cmds.add(SpawnUfo {
+            kind,
+            position: target
+                + Vec2::new(-heading * crossing / 2.0, rng.value_range(-1500.0, 1500.0)),
+            heading,
+            lifetime: crossing / kind.speed(),
+            inaccuracy,
+        });
+    }
+}
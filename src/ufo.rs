@@ -0,0 +1,207 @@
+use std::f32::consts::PI;
+
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+
+use crate::{player::KillPlayerOnTouch, prelude::*, score::MoneyDrop, shooter::shooter_fire};
+
+/// Distance players have to be within for saucers to open fire.
+pub const UFO_FIRING_RANGE: f32 = 3500.0;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Ufo>().register_type::<UfoGun>();
+
+    app.add_systems(
+        FixedUpdate,
+        ufo_aim.before(shooter_fire).in_set(GameplaySet),
+    );
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UfoKind {
+    /// Slow and sloppy, shoots anywhere.
+    Large,
+    /// Fast and aims at players.
+    Small,
+}
+impl UfoKind {
+    pub fn radius(self) -> f32 {
+        match self {
+            UfoKind::Large => 90.0,
+            UfoKind::Small => 50.0,
+        }
+    }
+
+    pub fn health(self) -> f32 {
+        match self {
+            UfoKind::Large => 6.0,
+            UfoKind::Small => 3.0,
+        }
+    }
+
+    pub fn speed(self) -> f32 {
+        match self {
+            UfoKind::Large => 350.0,
+            UfoKind::Small => 550.0,
+        }
+    }
+
+    pub fn money(self) -> u32 {
+        match self {
+            UfoKind::Large => 5,
+            UfoKind::Small => 10,
+        }
+    }
+
+    pub fn weapon(self) -> &'static str {
+        match self {
+            UfoKind::Large => "weapons/ufo_large.weapon.ron",
+            UfoKind::Small => "weapons/ufo_small.weapon.ron",
+        }
+    }
+
+    pub fn color(self) -> Srgba {
+        match self {
+            UfoKind::Large => CRIMSON,
+            UfoKind::Small => ORANGE_RED,
+        }
+    }
+}
+
+/// Hostile saucer crossing the arena, wandering about and steering clear of asteroids on the way.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Ufo {
+    pub kind: UfoKind,
+}
+
+/// Turret of a saucer, aims its [`Shooter`] at the closest player.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct UfoGun {
+    /// Largest angle in radians shots can miss by.
+    pub inaccuracy: f32,
+    pub aim_error: f32,
+    pub aim_timer: Timer,
+}
+
+pub struct SpawnUfo {
+    pub kind: UfoKind,
+    pub position: Vec2,
+    /// Horizontal direction to cross the arena in, -1 or 1.
+    pub heading: f32,
+    /// Seconds before it leaves.
+    pub lifetime: f32,
+    /// Largest angle in radians shots can miss by.
+    pub inaccuracy: f32,
+}
+impl Command for SpawnUfo {
+    fn apply(self, world: &mut World) {
+        world.run_system_once_with(self, spawn_ufo);
+    }
+}
+fn spawn_ufo(
+    spawn: In<SpawnUfo>,
+    mut cmds: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    r_assets: Res<AssetServer>,
+) {
+    let kind = spawn.kind;
+    let radius = kind.radius();
+    let heading = Vec2::X * spawn.heading.signum();
+    // Heads for the far side, about where it should be by the time it leaves.
+    let exit = spawn.position + heading * kind.speed() * spawn.lifetime;
+    cmds.spawn((
+        Name::new(format!("Ufo - {kind:?}")),
+        StateScoped(GameStates::Match),
+        Ufo { kind },
+        Health::new(kind.health()),
+        MoneyDrop(kind.money()),
+        KillPlayerOnTouch,
+        Knockback::new(20.0),
+        FactionBundle::body(Faction::Enemy),
+        Lifetime::new(spawn.lifetime),
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Ellipse::new(radius, radius * 0.4))),
+            material: materials.add(Color::from(kind.color())),
+            transform: Transform::from_translation(spawn.position.extend(0.0)),
+            ..default()
+        },
+        PhysicsBundle {
+            rigidbody: RigidBody::Dynamic,
+            collider: Collider::capsule_x(radius * 0.6, radius * 0.4),
+            gravity: GravityScale(0.0),
+            velocity: Velocity::linear(heading * kind.speed()),
+            ..default()
+        },
+        LockedAxes::ROTATION_LOCKED,
+        DestroyOnDeath,
+        (
+            Steering::new(kind.speed(), kind.speed()).with_output(SteeringOutput::Force),
+            ExternalForce::default(),
+            ReadMassProperties::default(),
+            Seek::new(SteeringTarget::Point(exit)),
+            Wander::new(600.0, 300.0, 4.0),
+            AvoidObstacles {
+                weight: 3.0,
+                ..AvoidObstacles::new(1.0, radius, ENVIRONMENT_GROUP)
+            },
+            Separation::new(radius * 6.0),
+        ),
+    ))
+    .with_children(|ufo| {
+        ufo.spawn(MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Circle::new(radius * 0.4))),
+            material: materials.add(Color::from(LIGHT_GRAY)),
+            transform: Transform::from_xyz(0.0, radius * 0.2, -0.1),
+            ..default()
+        });
+
+        let mut shooter = Shooter::new(r_assets.load(kind.weapon()));
+        // Don't shoot right as it shows up.
+        shooter.shoot_timer = -1.0;
+        ufo.spawn((
+            Name::new("Ufo Gun"),
+            UfoGun {
+                inaccuracy: spawn.inaccuracy,
+                aim_error: 0.0,
+                aim_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+            },
+            shooter,
+            Faction::Enemy,
+            SpatialBundle::default(),
+        ));
+    });
+}
+
+fn ufo_aim(
+    r_time: Res<Time>,
+    mut r_rng: ResMut<GameRng>,
+    q_players: Query<(&Player, &GlobalTransform)>,
+    mut q_guns: Query<(&mut UfoGun, &mut Shooter, &mut Transform, &GlobalTransform)>,
+) {
+    let rng = r_rng.stream(RngStream::Enemies);
+    for (mut gun, mut shooter, mut xform, gun_xform) in q_guns.iter_mut() {
+        if gun.aim_timer.tick(r_time.delta()).just_finished() {
+            gun.aim_error = rng.value_one() * gun.inaccuracy;
+        }
+
+        let position = gun_xform.translation().xy();
+        // Ties broken by index so the target doesn't depend on the query order.
+        let target = q_players
+            .iter()
+            .map(|(player, player_xform)| {
+                let to_player = player_xform.translation().xy() - position;
+                (to_player.length_squared(), player.index, to_player)
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+            .map(|(_, _, to_player)| to_player)
+            .filter(|to_player| to_player.length() <= UFO_FIRING_RANGE);
+
+        shooter.enabled = target.is_some();
+        if let Some(to_player) = target {
+            let aim = Vec2::from_angle(gun.aim_error).rotate(to_player.normalize_or(Vec2::Y));
+            xform.rotation = Quat::from_rotation_z(aim.to_angle() - PI / 2.0);
+        }
+    }
+}
@@ -1,8 +1,14 @@
+mod damage_numbers;
 mod hittable_button;
 mod main_menu;
+mod rebind_menu;
 
 use crate::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins(main_menu::plugin);
+    app.add_plugins((
+        damage_numbers::plugin,
+        main_menu::plugin,
+        rebind_menu::plugin,
+    ));
 }
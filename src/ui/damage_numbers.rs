@@ -0,0 +1,123 @@
+use crate::prelude::*;
+
+/// Seconds a number stays on screen.
+const LIFETIME: f32 = 0.8;
+/// Seconds the pop lasts, from `POP_SCALE` back to normal size.
+const POP_TIME: f32 = 0.12;
+const POP_SCALE: f32 = 1.8;
+const CRIT_SCALE: f32 = 1.5;
+/// Pixels per second numbers float up.
+const DRIFT_SPEED: f32 = 120.0;
+/// Damage on a target with a number younger than this adds to it instead of popping a new one.
+const MERGE_WINDOW: f32 = 0.15;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<PendingDamage>();
+
+    app.observe(queue_damage_number);
+    app.add_systems(
+        Update,
+        (spawn_damage_numbers, animate_damage_numbers).chain(),
+    );
+}
+
+/// Damage dealt since the last frame, waiting to be shown.
+#[derive(Resource, Default)]
+struct PendingDamage(Vec<(Entity, HitData)>);
+
+#[derive(Component)]
+struct DamageNumber {
+    target: Entity,
+    damage_type: DamageType,
+    amount: f32,
+    crit: bool,
+    age: f32,
+}
+impl DamageNumber {
+    fn text(&self) -> String {
+        let suffix = if self.crit { "!" } else { "" };
+        format!("{:.1}{suffix}", self.amount)
+    }
+}
+
+fn damage_color(damage_type: DamageType) -> Color {
+    match damage_type {
+        DamageType::Kinetic => WHITE.into(),
+        DamageType::Explosive => ORANGE.into(),
+        DamageType::Energy => DEEP_SKY_BLUE.into(),
+    }
+}
+
+fn queue_damage_number(e_damaged: Trigger<OnDamaged>, mut r_pending: ResMut<PendingDamage>) {
+    r_pending.0.push((e_damaged.entity(), e_damaged.event().0));
+}
+
+fn spawn_damage_numbers(
+    mut cmds: Commands,
+    mut r_pending: ResMut<PendingDamage>,
+    mut q_numbers: Query<(&mut DamageNumber, &mut Text)>,
+) {
+    for (target, hit) in r_pending.0.drain(..) {
+        let recent = q_numbers.iter_mut().find(|(number, _)| {
+            number.target == target
+                && number.damage_type == hit.damage_type
+                && number.age < MERGE_WINDOW
+        });
+        if let Some((mut number, mut text)) = recent {
+            number.amount += hit.damage;
+            number.crit |= hit.crit;
+            text.sections[0].value = number.text();
+            continue;
+        }
+
+        let number = DamageNumber {
+            target,
+            damage_type: hit.damage_type,
+            amount: hit.damage,
+            crit: hit.crit,
+            age: 0.0,
+        };
+        cmds.spawn((
+            Name::new("Damage Number"),
+            Text2dBundle {
+                text: Text::from_section(
+                    number.text(),
+                    TextStyle {
+                        font_size: 48.0,
+                        color: damage_color(hit.damage_type),
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(hit.point.xy().extend(10.0))
+                    .with_scale(Vec3::splat(POP_SCALE)),
+                ..default()
+            },
+            number,
+        ));
+    }
+}
+
+fn animate_damage_numbers(
+    mut cmds: Commands,
+    r_time: Res<Time>,
+    mut q_numbers: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+) {
+    let dt = r_time.delta_seconds();
+    for (e, mut number, mut xform, mut text) in q_numbers.iter_mut() {
+        number.age += dt;
+        if number.age >= LIFETIME {
+            cmds.entity(e).despawn();
+            continue;
+        }
+
+        let pop = (number.age / POP_TIME).min(1.0);
+        let crit_scale = if number.crit { CRIT_SCALE } else { 1.0 };
+        xform.scale = Vec3::splat(POP_SCALE.lerp(1.0, pop) * crit_scale);
+        xform.translation.y += DRIFT_SPEED * dt;
+
+        // Fade out over the second half.
+        let alpha = (2.0 * (1.0 - number.age / LIFETIME)).min(1.0);
+        let color = damage_color(number.damage_type).with_alpha(alpha);
+        text.sections[0].style.color = color;
+    }
+}
@@ -61,6 +61,7 @@ impl<'w, 's> SpawnHittableButtonExt for Commands<'w, 's> {
 
         self.spawn((
             HittableButton { distance: 500.0 },
+            Health::new(1.0),
             ParticleEffectBundle {
                 effect: ParticleEffect::new(r_effects.add(effect)),
                 transform: Transform::from_translation(Vec3::new(0.0, 500.0, 0.0)),
@@ -1,6 +1,6 @@
 use std::f32::consts::PI;
 
-use crate::prelude::*;
+use crate::{player::players_center, prelude::*};
 
 use super::hittable_button::{HittableButton, SpawnHittableButtonExt};
 
@@ -8,7 +8,7 @@ pub mod prelude {}
 
 pub fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameStates::Menu), setup_main_menu);
-    app.add_systems(Update, update_menu_buttons);
+    app.add_systems(FixedUpdate, update_menu_buttons.in_set(GameplaySet));
 }
 
 fn setup_main_menu(
@@ -23,7 +23,7 @@ fn setup_main_menu(
         "Play!".into(),
     );
     cmds.entity(play_button)
-        .insert(Name::new("Play!"))
+        .insert((Name::new("Play!"), StateScoped(GameStates::Menu)))
         .observe(on_play_pressed);
 
     let exit_button = cmds.spawn_hittable_button(
@@ -33,29 +33,31 @@ fn setup_main_menu(
         "Exit".into(),
     );
     cmds.entity(exit_button)
-        .insert(Name::new("Exit"))
+        .insert((Name::new("Exit"), StateScoped(GameStates::Menu)))
         .observe(on_exit_pressed);
 }
 
 fn update_menu_buttons(
     mut cmds: Commands,
     q_player: Query<&GlobalTransform, With<Player>>,
-    mut gizmos: Gizmos,
     mut q_buttons: Query<(Entity, &mut Transform, &HittableButton)>,
 ) {
-    let player = q_player.single();
+    let Some(center) = players_center(q_player.iter()) else {
+        return;
+    };
 
     let button_count = q_buttons.iter().len();
     let angle_dt = f32::to_radians(360.0 / button_count as f32);
     for (i, (e, mut xform, button)) in q_buttons.iter_mut().enumerate() {
         let angle = angle_dt * i as f32;
-        let target = player.translation() + Vec2::from_angle(angle).extend(0.0) * button.distance;
+        let target =
+            (center + Vec2::from_angle(angle) * button.distance).extend(xform.translation.z);
         xform.translation = xform.translation.lerp(target, 0.1);
     }
 }
 
 fn on_play_pressed(
-    e_hit: Trigger<OnHit>,
+    e_death: Trigger<OnDeath>,
     mut cmds: Commands,
     mut q_vfx: Query<&mut EffectSpawner>,
     mut r_state: ResMut<NextState<GameStates>>,
@@ -64,11 +66,11 @@ fn on_play_pressed(
     r_state.set(GameStates::Match);
 
     // TODO: Add VFXs
-    if let Ok(mut vfx) = q_vfx.get_mut(e_hit.entity()) {
+    if let Ok(mut vfx) = q_vfx.get_mut(e_death.entity()) {
         vfx.reset();
     }
 
-    cmds.entity(e_hit.entity())
+    cmds.entity(e_death.entity())
         .despawn_descendants()
         .insert(Lifetime::new(1.0));
 
@@ -76,7 +78,7 @@ fn on_play_pressed(
 }
 
 fn on_exit_pressed(
-    e_hit: Trigger<OnHit>,
+    e_death: Trigger<OnDeath>,
     mut cmds: Commands,
     mut app_exit_events: ResMut<Events<bevy::app::AppExit>>,
     q_buttons: Query<Entity, With<HittableButton>>,
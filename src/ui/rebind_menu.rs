@@ -0,0 +1,121 @@
+use crate::{
+    input::{ActionMap, InputAction, PendingRebind, RebindAction},
+    prelude::*,
+};
+
+/// Key opening and closing the screen. Not an action itself so it can't be unbound.
+const TOGGLE_KEY: KeyCode = KeyCode::F1;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (toggle_rebind_menu, select_rebind_action, update_rebind_menu).chain(),
+    );
+}
+
+/// Lists the bindings of every action. Tab picks an action, Enter binds the next key or
+/// button pressed to it.
+#[derive(Component, Default)]
+struct RebindMenu {
+    selected: usize,
+}
+
+/// Actions that can be bound to buttons. Aiming follows the cursor or stick instead.
+fn rebindable_actions() -> impl Iterator<Item = InputAction> {
+    InputAction::ALL
+        .into_iter()
+        .filter(|action| *action != InputAction::Aim)
+}
+
+fn toggle_rebind_menu(
+    mut cmds: Commands,
+    r_keys: Res<ButtonInput<KeyCode>>,
+    r_pending: Res<PendingRebind>,
+    q_menus: Query<Entity, With<RebindMenu>>,
+) {
+    if !r_keys.just_pressed(TOGGLE_KEY) || r_pending.0.is_some() {
+        return;
+    }
+
+    if let Ok(menu) = q_menus.get_single() {
+        cmds.entity(menu).despawn_recursive();
+        return;
+    }
+
+    cmds.spawn((
+        Name::new("Rebind Menu"),
+        RebindMenu::default(),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 20.0,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            left: Val::Px(20.0),
+            ..default()
+        })
+        .with_background_color(Color::BLACK.with_alpha(0.7)),
+    ));
+}
+
+fn select_rebind_action(
+    r_keys: Res<ButtonInput<KeyCode>>,
+    r_pending: Res<PendingRebind>,
+    mut e_rebind: EventWriter<RebindAction>,
+    mut q_menus: Query<&mut RebindMenu>,
+) {
+    let Ok(mut menu) = q_menus.get_single_mut() else {
+        return;
+    };
+    // Keys pressed while waiting for a binding belong to it.
+    if r_pending.0.is_some() {
+        return;
+    }
+
+    let count = rebindable_actions().count();
+    if r_keys.just_pressed(KeyCode::Tab) {
+        let back = r_keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        menu.selected = if back {
+            (menu.selected + count - 1) % count
+        } else {
+            (menu.selected + 1) % count
+        };
+    }
+
+    if r_keys.just_pressed(KeyCode::Enter) {
+        if let Some(action) = rebindable_actions().nth(menu.selected) {
+            e_rebind.send(RebindAction(action));
+        }
+    }
+}
+
+fn update_rebind_menu(
+    r_map: Res<ActionMap>,
+    r_pending: Res<PendingRebind>,
+    mut q_menus: Query<(&RebindMenu, &mut Text)>,
+) {
+    let Ok((menu, mut text)) = q_menus.get_single_mut() else {
+        return;
+    };
+
+    let mut lines = vec!["Tab: select, Enter: rebind, F1: close".to_string()];
+    for (i, action) in rebindable_actions().enumerate() {
+        let cursor = if i == menu.selected { ">" } else { " " };
+        let bindings = if r_pending.0 == Some(action) {
+            "press a key or button, Esc cancels".to_string()
+        } else {
+            let bindings: Vec<_> = r_map
+                .bindings(action)
+                .iter()
+                .map(|binding| format!("{binding:?}"))
+                .collect();
+            bindings.join(", ")
+        };
+        lines.push(format!("{cursor} {action:?}: {bindings}"));
+    }
+    text.sections[0].value = lines.join("\n");
+}
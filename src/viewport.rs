@@ -0,0 +1,46 @@
+use bevy::window::PrimaryWindow;
+
+use crate::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<VirtualViewport>();
+    app.init_resource::<VirtualViewport>();
+
+    app.add_systems(PreUpdate, sync_viewport_with_window);
+}
+
+/// Screen information gameplay reads instead of querying the window directly.
+/// Kept in sync with the primary window when there is one, otherwise it's up to
+/// whoever drives the app (e.g. headless simulations) to fill it.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct VirtualViewport {
+    pub size: Vec2,
+    /// Cursor position in world space.
+    pub cursor: Option<Vec2>,
+}
+impl Default for VirtualViewport {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(1280.0, 720.0),
+            cursor: None,
+        }
+    }
+}
+
+pub(crate) fn sync_viewport_with_window(
+    mut r_viewport: ResMut<VirtualViewport>,
+    q_wnd: Query<&Window, With<PrimaryWindow>>,
+    q_cam: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+) {
+    let Ok(window) = q_wnd.get_single() else {
+        return;
+    };
+
+    r_viewport.size = window.size();
+    r_viewport.cursor = q_cam.get_single().ok().and_then(|(camera, camera_xform)| {
+        window
+            .cursor_position()
+            .and_then(|cursor| camera.viewport_to_world_2d(camera_xform, cursor))
+    });
+}
@@ -0,0 +1,242 @@
+pub mod arsenal;
+pub mod beam;
+pub mod inventory;
+pub use arsenal::*;
+pub use beam::*;
+pub use inventory::*;
+
+use std::{f32::consts::PI, fmt, io};
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.init_asset::<WeaponDef>()
+        .register_asset_loader(WeaponDefLoader);
+
+    // The arsenal loads weapons right away, so the asset type must be known first.
+    app.add_plugins((arsenal::plugin, beam::plugin, inventory::plugin));
+}
+
+/// Everything about how a [`Shooter`] fires, loaded from `*.weapon.ron` files.
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct WeaponDef {
+    pub name: String,
+    /// Shots per second, a burst counts as one shot.
+    pub fire_rate: f32,
+    /// Projectiles fired at once, spread evenly over `spread`.
+    #[serde(default = "one")]
+    pub projectile_count: u32,
+    /// Angle in degrees between the outermost projectiles of a shot.
+    #[serde(default)]
+    pub spread: f32,
+    /// Shots fired in a row every time the weapon fires.
+    #[serde(default = "one")]
+    pub burst_size: u32,
+    /// Seconds between the shots of a burst.
+    #[serde(default)]
+    pub burst_delay: f32,
+    #[serde(default)]
+    pub speed: f32,
+    #[serde(default)]
+    pub damage: f32,
+    /// Chance between 0 and 1 for hits to be critical.
+    #[serde(default)]
+    pub crit_chance: f32,
+    #[serde(default = "default_crit_multiplier")]
+    pub crit_multiplier: f32,
+    /// Impulse pushing the shooter back on every shot.
+    #[serde(default)]
+    pub recoil: f32,
+    /// Distance projectiles travel before disappearing.
+    pub range: f32,
+    /// Makes the damage drop the further projectiles travel.
+    #[serde(default)]
+    pub falloff: Option<FalloffDef>,
+    /// Where projectiles come out in the shooter's space, each shot uses the next one.
+    pub muzzles: Vec<Vec2>,
+    #[serde(default)]
+    pub projectile: ProjectileVisual,
+    /// Makes the projectiles seek targets.
+    #[serde(default)]
+    pub homing: Option<HomingDef>,
+    /// Targets each projectile goes through.
+    #[serde(default)]
+    pub pierce: u32,
+    /// Times each projectile bounces off targets it doesn't pierce.
+    #[serde(default)]
+    pub ricochet: u32,
+    #[serde(default)]
+    pub split: Option<SplitDef>,
+    /// Makes the projectiles explode once spent or expired.
+    #[serde(default)]
+    pub explosive: Option<ExplosiveDef>,
+    /// Fires a continuous beam up to `range` instead of projectiles.
+    #[serde(default)]
+    pub beam: Option<BeamDef>,
+}
+impl WeaponDef {
+    pub fn shot_delay(&self) -> f32 {
+        1.0 / self.fire_rate.max(f32::EPSILON)
+    }
+
+    pub fn muzzle(&self, index: usize) -> Vec2 {
+        if self.muzzles.is_empty() {
+            Vec2::ZERO
+        } else {
+            self.muzzles[index % self.muzzles.len()]
+        }
+    }
+
+    /// Direction of each projectile of a shot, rotated from `forward`.
+    pub fn spread_directions(&self, forward: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        let count = self.projectile_count.max(1);
+        let spread = self.spread * PI / 180.0;
+        (0..count).map(move |i| {
+            let angle = if count > 1 {
+                -spread / 2.0 + spread * i as f32 / (count - 1) as f32
+            } else {
+                0.0
+            };
+            Vec2::from_angle(angle).rotate(forward)
+        })
+    }
+
+    /// Backstop for projectiles slowed down or circling around, [`TravelRange`](crate::projectiles::TravelRange) usually
+    /// expires them way before.
+    pub fn projectile_lifetime(&self) -> f32 {
+        2.0 * self.range / self.speed.max(f32::EPSILON)
+    }
+}
+
+fn one() -> u32 {
+    1
+}
+
+fn default_crit_multiplier() -> f32 {
+    2.0
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectileVisual {
+    pub radius: f32,
+    pub color: Color,
+}
+impl Default for ProjectileVisual {
+    fn default() -> Self {
+        Self {
+            radius: 10.0,
+            color: Color::WHITE,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FalloffDef {
+    /// Distance travelled the damage starts dropping at.
+    pub start: f32,
+    /// Fraction of the damage left at the end of the range.
+    pub min_damage: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct HomingDef {
+    /// Degrees per second the projectile can turn.
+    pub turn_rate: f32,
+    pub seek_radius: f32,
+    /// Degrees to either side of the heading targets are picked from.
+    pub seek_angle: f32,
+    /// Seconds of steering before the projectile expires.
+    pub fuel: f32,
+}
+
+/// Shards a projectile bursts into on its first hit.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SplitDef {
+    pub shards: u32,
+    /// Degrees between the outermost shards.
+    pub spread: f32,
+    /// Fraction of the projectile's damage each shard deals.
+    pub damage: f32,
+    /// Size of the shards relative to the projectile.
+    pub scale: f32,
+    pub lifetime: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExplosiveDef {
+    pub radius: f32,
+    pub damage: f32,
+    /// Fraction of the damage and impulse left at the edge of the radius.
+    #[serde(default = "one_f32")]
+    pub falloff: f32,
+    #[serde(default)]
+    pub impulse: f32,
+    /// Fraction of the damage dealt to whoever fired it.
+    #[serde(default)]
+    pub self_damage: f32,
+}
+
+fn one_f32() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BeamDef {
+    pub damage_per_second: f32,
+    /// Targets the beam goes through before stopping.
+    #[serde(default)]
+    pub pierce: u32,
+    pub width: f32,
+    pub color: Color,
+}
+
+#[derive(Default)]
+pub struct WeaponDefLoader;
+impl AssetLoader for WeaponDefLoader {
+    type Asset = WeaponDef;
+    type Settings = ();
+    type Error = WeaponDefError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<WeaponDef, WeaponDefError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["weapon.ron"]
+    }
+}
+
+#[derive(Debug)]
+pub enum WeaponDefError {
+    Io(io::Error),
+    Format(ron::error::SpannedError),
+}
+impl fmt::Display for WeaponDefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeaponDefError::Io(err) => write!(f, "{err}"),
+            WeaponDefError::Format(err) => write!(f, "{err}"),
+        }
+    }
+}
+impl std::error::Error for WeaponDefError {}
+impl From<io::Error> for WeaponDefError {
+    fn from(err: io::Error) -> Self {
+        WeaponDefError::Io(err)
+    }
+}
+impl From<ron::error::SpannedError> for WeaponDefError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        WeaponDefError::Format(err)
+    }
+}
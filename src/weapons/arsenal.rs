@@ -0,0 +1,66 @@
+use crate::prelude::*;
+
+use super::{GiveWeapon, WeaponInventory};
+
+/// Dev shortcut handing out the next weapon until pickups or a shop do it. Not an action so
+/// it stays out of the rebinding screen.
+const GIVE_WEAPON_KEY: KeyCode = KeyCode::F2;
+
+/// Every weapon players can carry, in the order the dev shortcut hands them out.
+const PLAYER_WEAPONS: [&str; 7] = [
+    "weapons/blaster.weapon.ron",
+    "weapons/twin_burst.weapon.ron",
+    "weapons/scattergun.weapon.ron",
+    "weapons/prism_rifle.weapon.ron",
+    "weapons/mining_laser.weapon.ron",
+    "weapons/missile_launcher.weapon.ron",
+    "weapons/rocket_launcher.weapon.ron",
+];
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<Arsenal>();
+    app.add_systems(Update, give_next_weapon);
+}
+
+/// Handles to every player weapon, kept loaded so giving one out is instant.
+#[derive(Resource)]
+pub struct Arsenal {
+    pub weapons: Vec<Handle<WeaponDef>>,
+}
+impl FromWorld for Arsenal {
+    fn from_world(world: &mut World) -> Self {
+        let r_assets = world.resource::<AssetServer>();
+        Self {
+            weapons: PLAYER_WEAPONS
+                .iter()
+                .map(|path| r_assets.load(*path))
+                .collect(),
+        }
+    }
+}
+impl Arsenal {
+    /// First weapon of the arsenal missing from the inventory.
+    pub fn next_for(&self, inventory: &WeaponInventory) -> Option<Handle<WeaponDef>> {
+        self.weapons
+            .iter()
+            .find(|weapon| inventory.slot_of(weapon).is_none())
+            .cloned()
+    }
+}
+
+fn give_next_weapon(
+    mut cmds: Commands,
+    r_keys: Res<ButtonInput<KeyCode>>,
+    r_arsenal: Res<Arsenal>,
+    q_players: Query<(Entity, &WeaponInventory), With<Player>>,
+) {
+    if !r_keys.just_pressed(GIVE_WEAPON_KEY) {
+        return;
+    }
+
+    for (e, inventory) in q_players.iter() {
+        if let Some(weapon) = r_arsenal.next_for(inventory) {
+            cmds.trigger_targets(GiveWeapon(weapon), e);
+        }
+    }
+}
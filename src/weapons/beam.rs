@@ -0,0 +1,185 @@
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+
+use crate::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<BeamState>();
+
+    app.add_systems(FixedUpdate, fire_beams.in_set(GameplaySet));
+    app.add_systems(Update, (add_beam_visuals, update_beam_visuals).chain());
+}
+
+/// How far the beam of a [`Shooter`] with a beam weapon reached on the last step.
+#[derive(Component, Reflect, Default, Clone)]
+#[reflect(Component)]
+pub struct BeamState {
+    pub firing: bool,
+    /// Distance from the muzzle to where the beam stops.
+    pub length: f32,
+    /// Whether the beam stopped on something instead of fading out at its range.
+    pub impact: bool,
+}
+
+#[derive(Component)]
+pub struct BeamVisual {
+    pub part: BeamPart,
+    pub material: Handle<ColorMaterial>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BeamPart {
+    Ray,
+    Impact,
+}
+
+fn fire_beams(
+    mut cmds: Commands,
+    mut q_shooters: Query<(Entity, &GlobalTransform, &Shooter, Option<&mut BeamState>)>,
+    r_weapons: Res<Assets<WeaponDef>>,
+    r_rapier: Res<RapierContext>,
+    r_time: Res<Time>,
+    mut r_rng: ResMut<GameRng>,
+    factions: Factions,
+) {
+    for (e, xform, shooter, state) in q_shooters.iter_mut() {
+        let beam = r_weapons
+            .get(&shooter.weapon)
+            .and_then(|weapon| weapon.beam.as_ref().map(|beam| (weapon, beam)));
+
+        let mut new_state = BeamState::default();
+        if let Some((weapon, beam)) = beam.filter(|_| shooter.enabled) {
+            let origin = xform.transform_point(weapon.muzzle(0).extend(0.0)).xy();
+            let dir = xform.up();
+            new_state = BeamState {
+                firing: true,
+                length: weapon.range,
+                impact: false,
+            };
+
+            // Every ray stops at the first target, so cast again past it to pierce.
+            let mut hit = Vec::new();
+            loop {
+                // Goes through allies.
+                let not_hit = |target| !hit.contains(&target) && factions.can_damage(e, target);
+                let filter = QueryFilter::default()
+                    .exclude_sensors()
+                    .exclude_collider(e)
+                    .predicate(&not_hit);
+                let Some((target, toi)) =
+                    r_rapier.cast_ray(origin, dir.xy(), weapon.range, true, filter)
+                else {
+                    break;
+                };
+
+                let mut hit_data = HitData {
+                    point: (origin + dir.xy() * toi).extend(0.0),
+                    dir,
+                    dealer: e,
+                    damage: beam.damage_per_second * r_time.delta_seconds(),
+                    damage_type: DamageType::Energy,
+                    crit: false,
+                    impulse: None,
+                    ignore_invulnerability: false,
+                };
+                Crit::new(weapon.crit_chance, weapon.crit_multiplier)
+                    .roll(&mut hit_data, &mut r_rng);
+                cmds.trigger_targets(OnHit(hit_data), target);
+
+                hit.push(target);
+                if hit.len() > beam.pierce as usize {
+                    new_state.length = toi;
+                    new_state.impact = true;
+                    break;
+                }
+            }
+        } else if state.is_none() {
+            continue;
+        }
+
+        match state {
+            Some(mut state) => *state = new_state,
+            None => {
+                cmds.entity(e).insert(new_state);
+            }
+        }
+    }
+}
+
+fn add_beam_visuals(
+    mut cmds: Commands,
+    q_beams: Query<Entity, Added<BeamState>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for e in q_beams.iter() {
+        for (part, mesh) in [
+            (BeamPart::Ray, meshes.add(Rectangle::new(1.0, 1.0))),
+            (BeamPart::Impact, meshes.add(Circle::new(0.5))),
+        ] {
+            let material = materials.add(Color::WHITE);
+            let visual = cmds
+                .spawn((
+                    Name::new("Beam"),
+                    BeamVisual {
+                        part,
+                        material: material.clone(),
+                    },
+                    MaterialMesh2dBundle {
+                        mesh: Mesh2dHandle(mesh),
+                        material,
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                ))
+                .id();
+            cmds.entity(e).add_child(visual);
+        }
+    }
+}
+
+fn update_beam_visuals(
+    q_beams: Query<(&BeamState, &Shooter)>,
+    mut q_visuals: Query<(&Parent, &BeamVisual, &mut Transform, &mut Visibility)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    r_weapons: Res<Assets<WeaponDef>>,
+    r_time: Res<Time>,
+) {
+    for (parent, visual, mut xform, mut visibility) in q_visuals.iter_mut() {
+        let Ok((state, shooter)) = q_beams.get(parent.get()) else {
+            continue;
+        };
+        let beam = r_weapons
+            .get(&shooter.weapon)
+            .and_then(|weapon| weapon.beam.as_ref().map(|beam| (weapon, beam)));
+        let Some((weapon, beam)) = beam.filter(|_| state.firing) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        // Beams are built along the shooter's up, starting at its first muzzle.
+        let muzzle = weapon.muzzle(0);
+        match visual.part {
+            BeamPart::Ray => {
+                xform.translation = (muzzle + Vec2::Y * state.length / 2.0).extend(-0.1);
+                xform.scale = Vec3::new(beam.width, state.length, 1.0);
+                *visibility = Visibility::Inherited;
+            }
+            BeamPart::Impact => {
+                let pulse = 1.0 + 0.3 * (r_time.elapsed_seconds() * 40.0).sin();
+                xform.translation = (muzzle + Vec2::Y * state.length).extend(0.1);
+                xform.scale = Vec3::splat(beam.width * 2.5 * pulse);
+                *visibility = if state.impact {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
+        }
+
+        if let Some(material) = materials.get_mut(&visual.material) {
+            if material.color != beam.color {
+                material.color = beam.color;
+            }
+        }
+    }
+}
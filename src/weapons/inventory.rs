@@ -0,0 +1,165 @@
+use crate::{input::PlayerInputs, prelude::*};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<WeaponInventory>();
+
+    app.add_systems(
+        FixedUpdate,
+        (player_switch_weapon, recharge_holstered_weapons).in_set(GameplaySet),
+    );
+
+    app.observe(give_weapon);
+}
+
+/// A weapon in the inventory and the state of its [`Shooter`] while holstered. The cooldown
+/// keeps running in the holster, up to a ready shot.
+#[derive(Reflect, Clone, Default)]
+pub struct WeaponSlot {
+    pub weapon: Handle<WeaponDef>,
+    pub shoot_timer: f32,
+    pub muzzle_index: usize,
+}
+
+/// Weapons carried by a ship. The active one is the weapon of its [`Shooter`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct WeaponInventory {
+    pub slots: Vec<WeaponSlot>,
+    pub active: usize,
+    pub max_slots: usize,
+    /// Switch buttons held on the previous step, cycling only happens on a new press.
+    pub was_next_pressed: bool,
+    pub was_previous_pressed: bool,
+}
+impl WeaponInventory {
+    pub fn new(weapon: Handle<WeaponDef>) -> Self {
+        Self {
+            slots: vec![WeaponSlot {
+                weapon,
+                ..default()
+            }],
+            active: 0,
+            max_slots: 4,
+            was_next_pressed: false,
+            was_previous_pressed: false,
+        }
+    }
+
+    pub fn slot_of(&self, weapon: &Handle<WeaponDef>) -> Option<usize> {
+        self.slots.iter().position(|slot| slot.weapon == *weapon)
+    }
+
+    /// Puts the weapon in a free slot and returns it, none if already carried or full.
+    pub fn add(&mut self, weapon: Handle<WeaponDef>) -> Option<usize> {
+        if self.slot_of(&weapon).is_some() || self.slots.len() >= self.max_slots {
+            return None;
+        }
+
+        self.slots.push(WeaponSlot {
+            weapon,
+            ..default()
+        });
+        Some(self.slots.len() - 1)
+    }
+
+    /// Holsters the active weapon and hands the one in `slot` to the shooter. Returns false
+    /// if there's nothing to switch to.
+    pub fn switch(&mut self, shooter: &mut Shooter, slot: usize) -> bool {
+        if slot == self.active || slot >= self.slots.len() {
+            return false;
+        }
+
+        if let Some(holstered) = self.slots.get_mut(self.active) {
+            holstered.shoot_timer = shooter.shoot_timer;
+            holstered.muzzle_index = shooter.muzzle_index;
+        }
+
+        self.active = slot;
+        let drawn = &self.slots[slot];
+        shooter.weapon = drawn.weapon.clone();
+        shooter.shoot_timer = drawn.shoot_timer;
+        shooter.muzzle_index = drawn.muzzle_index;
+        shooter.burst_left = 0;
+        true
+    }
+}
+
+/// Trigger on a ship to add a weapon to its inventory, e.g. from a pickup or the shop.
+#[derive(Event)]
+pub struct GiveWeapon(pub Handle<WeaponDef>);
+
+#[derive(Event)]
+pub struct OnWeaponAcquired {
+    pub slot: usize,
+    pub weapon: Handle<WeaponDef>,
+}
+
+#[derive(Event)]
+pub struct OnWeaponSwitched {
+    pub slot: usize,
+    pub weapon: Handle<WeaponDef>,
+}
+
+fn give_weapon(
+    e_give: Trigger<GiveWeapon>,
+    mut cmds: Commands,
+    mut q_inventories: Query<&mut WeaponInventory>,
+) {
+    let Ok(mut inventory) = q_inventories.get_mut(e_give.entity()) else {
+        return;
+    };
+
+    let weapon = e_give.event().0.clone();
+    if let Some(slot) = inventory.add(weapon.clone()) {
+        cmds.trigger_targets(OnWeaponAcquired { slot, weapon }, e_give.entity());
+    }
+}
+
+fn player_switch_weapon(
+    mut cmds: Commands,
+    mut q_players: Query<(Entity, &Player, &mut WeaponInventory, &mut Shooter)>,
+    r_inputs: Res<PlayerInputs>,
+) {
+    for (e, player, mut inventory, mut shooter) in q_players.iter_mut() {
+        let input = r_inputs.get(player.index);
+        let next = input.next_weapon && !inventory.was_next_pressed;
+        let previous = input.previous_weapon && !inventory.was_previous_pressed;
+        inventory.was_next_pressed = input.next_weapon;
+        inventory.was_previous_pressed = input.previous_weapon;
+
+        let count = inventory.slots.len();
+        let slot = if let Some(slot) = input.weapon_slot {
+            slot
+        } else if next {
+            (inventory.active + 1) % count.max(1)
+        } else if previous {
+            (inventory.active + count.saturating_sub(1)) % count.max(1)
+        } else {
+            continue;
+        };
+
+        if inventory.switch(&mut shooter, slot) {
+            let weapon = shooter.weapon.clone();
+            cmds.trigger_targets(OnWeaponSwitched { slot, weapon }, e);
+        }
+    }
+}
+
+fn recharge_holstered_weapons(
+    mut q_inventories: Query<&mut WeaponInventory>,
+    r_weapons: Res<Assets<WeaponDef>>,
+    r_time: Res<Time>,
+) {
+    for mut inventory in q_inventories.iter_mut() {
+        let active = inventory.active;
+        for (i, slot) in inventory.slots.iter_mut().enumerate() {
+            let Some(weapon) = r_weapons.get(&slot.weapon) else {
+                continue;
+            };
+            if i != active && slot.shoot_timer < weapon.shot_delay() {
+                slot.shoot_timer =
+                    (slot.shoot_timer + r_time.delta_seconds()).min(weapon.shot_delay());
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use asteroids::{
+    headless,
+    input::{Aim, InputSet, PlayerInput, PlayerInputs},
+    prelude::*,
+    projectiles::Projectile,
+    weapons::{Arsenal, GiveWeapon, WeaponInventory},
+    AsteroidsPlugins, FIXED_TIMESTEP_HZ,
+};
+
+const SEED: u64 = 42;
+
+/// Builds the game on top of the headless engine setup, with a fixed seed.
+fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        headless::plugin,
+        AsteroidsPlugins::headless().without_engine(),
+    ));
+    app.world_mut().resource_mut::<GameRng>().reseed(SEED);
+    app
+}
+
+/// Input fed to the first player instead of the devices.
+#[derive(Resource, Default)]
+struct TestInput(PlayerInput);
+
+fn override_input(r_input: Res<TestInput>, mut r_inputs: ResMut<PlayerInputs>) {
+    r_inputs.0 = vec![r_input.0];
+}
+
+fn start_match(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<NextState<GameStates>>()
+        .set(GameStates::Match);
+    app.update();
+    app.update();
+}
+
+fn in_state(app: &App, state: GameStates) -> bool {
+    *app.world().resource::<State<GameStates>>() == state
+}
+
+fn count<T: Component>(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), With<T>>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn match_starts_and_spawns_asteroids() {
+    let mut app = headless_app();
+    start_match(&mut app);
+    assert!(in_state(&app, GameStates::Match));
+    assert_eq!(count::<Player>(&mut app), 1);
+
+    // The spawner sends its first wave after five seconds.
+    let steps = (6.0 * FIXED_TIMESTEP_HZ) as usize;
+    for _ in 0..steps {
+        app.update();
+        if count::<Asteroid>(&mut app) > 0 {
+            break;
+        }
+    }
+    assert!(count::<Asteroid>(&mut app) > 0);
+    assert!(app.world().resource::<Time<Fixed>>().elapsed_seconds() > 0.0);
+}
+
+#[test]
+fn given_weapons_can_be_switched_and_fired() {
+    let mut app = headless_app();
+    app.init_resource::<TestInput>()
+        .add_systems(FixedPreUpdate, override_input.in_set(InputSet::Override));
+    start_match(&mut app);
+
+    let player = app
+        .world_mut()
+        .query_filtered::<Entity, With<Player>>()
+        .single(app.world());
+    let scattergun = app.world().resource::<Arsenal>().weapons[2].clone();
+    app.world_mut()
+        .trigger_targets(GiveWeapon(scattergun.clone()), player);
+
+    let inventory = app.world().get::<WeaponInventory>(player).unwrap();
+    assert_eq!(inventory.slot_of(&scattergun), Some(1));
+
+    app.world_mut().resource_mut::<TestInput>().0.weapon_slot = Some(1);
+    app.update();
+    assert_eq!(
+        app.world().get::<WeaponInventory>(player).unwrap().active,
+        1
+    );
+    assert_eq!(
+        app.world().get::<Shooter>(player).unwrap().weapon,
+        scattergun
+    );
+
+    // Fires once the weapon finished loading, every pellet of the shot at once.
+    *app.world_mut().resource_mut::<TestInput>() = TestInput(PlayerInput {
+        fire: true,
+        aim: Some(Aim::Direction(Vec2::Y)),
+        ..default()
+    });
+    for _ in 0..(5.0 * FIXED_TIMESTEP_HZ) as usize {
+        app.update();
+        if count::<Projectile>(&mut app) > 0 {
+            break;
+        }
+    }
+    assert!(count::<Projectile>(&mut app) >= 5);
+}